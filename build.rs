@@ -0,0 +1,116 @@
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Build the exercises of info.toml into rustlings as they are before anyone
+// worked on them, so that `rustlings reset` can restore them even after they
+// were edited and committed. Each original is kept with the name and path of
+// its entry, which tell whether an exercise is the one that was built in.
+fn main() {
+    println!("cargo:rerun-if-changed=exercises");
+    println!("cargo:rerun-if-changed=info.toml");
+
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap());
+    // What is committed changes with HEAD and the index
+    for file in [".git/HEAD", ".git/index"] {
+        if root.join(file).is_file() {
+            println!("cargo:rerun-if-changed={file}");
+        }
+    }
+
+    // The name and path of each exercise, with its files by relative path
+    let mut code = String::from("type Files = &'static [(&'static str, &'static [u8])];\n");
+    code.push_str("const ORIGINALS: &[(&str, &str, Files)] = &[\n");
+    for (name, path) in entries(&root) {
+        let mut files = Vec::new();
+        if root.join(&path).is_dir() {
+            collect(&root, &path, &mut files);
+        } else if root.join(&path).is_file() {
+            files.push(path.clone());
+        }
+        if files.is_empty() {
+            continue;
+        }
+        files.sort();
+
+        writeln!(code, "    ({name:?}, {:?}, &[", key(&path)).unwrap();
+        for file in files {
+            let relative = file.strip_prefix(&path).unwrap_or(Path::new(""));
+            let copy = out.join("originals").join(&file);
+            fs::create_dir_all(copy.parent().unwrap()).unwrap();
+            fs::write(&copy, original(&root, &file)).unwrap();
+            writeln!(
+                code,
+                "        ({:?}, include_bytes!({copy:?})),",
+                key(relative)
+            )
+            .unwrap();
+        }
+        writeln!(code, "    ]),").unwrap();
+    }
+    code.push_str("];\n");
+
+    fs::write(out.join("originals.rs"), code).unwrap();
+}
+
+// The name and path of every exercise in info.toml
+fn entries(root: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(text) = fs::read_to_string(root.join("info.toml")) else {
+        return Vec::new();
+    };
+    let info: toml::Table = text.parse().expect("info.toml isn't valid TOML");
+    let exercises = info.get("exercises").and_then(|e| e.as_array());
+    exercises
+        .into_iter()
+        .flatten()
+        .filter_map(|exercise| {
+            let name = exercise.get("name")?.as_str()?;
+            let path = exercise.get("path")?.as_str()?;
+            Some((name.to_string(), PathBuf::from(path)))
+        })
+        .collect()
+}
+
+// The committed version of the file, so that exercises that were edited
+// before building aren't taken for originals. Outside of a git checkout,
+// like in a published package, the file is used as it is
+fn original(root: &Path, file: &Path) -> Vec<u8> {
+    let committed = Command::new("git")
+        .arg("show")
+        .arg(format!("HEAD:./{}", key(file)))
+        .current_dir(root)
+        .output();
+    match committed {
+        Ok(output) if output.status.success() => output.stdout,
+        _ => fs::read(root.join(file)).unwrap(),
+    }
+}
+
+// The path with forward slashes, the same on every platform
+fn key(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// The sources of the exercises, without what compiling them leaves behind
+fn collect(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(root.join(dir)).into_iter().flatten().flatten() {
+        let relative = dir.join(entry.file_name());
+        let path = entry.path();
+        if path.is_dir() {
+            if entry.file_name() != "target" {
+                collect(root, &relative, files);
+            }
+            continue;
+        }
+        let extension = path.extension().and_then(|e| e.to_str());
+        if !matches!(extension, None | Some("exe" | "pdb" | "lock")) {
+            files.push(relative);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use crate::config;
+use crate::exercise::{self, Exercise};
+use crate::util;
+use crate::verify::Outcome;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+
+const CACHE_FILE: &str = ".rustlings/cache.json";
+const RUSTLINGS_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+static CACHE: OnceLock<Mutex<Cache>> = OnceLock::new();
+
+/// Remembers the last outcome of checking each exercise, stored in
+/// `.rustlings/cache.json`, so that unchanged exercises aren't rebuilt.
+/// Outcomes are keyed on the exercise's source, its `info.toml` entry, its
+/// limits, the color setting and the version of its toolchain, and are all
+/// dropped whenever the output of `rustc --version` or the version of
+/// rustlings changes, as both may change how exercises are checked.
+#[derive(Serialize, Deserialize, Default)]
+struct Cache {
+    rustc_version: String,
+    #[serde(default)]
+    rustlings_version: String,
+    entries: HashMap<String, Entry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    key: String,
+    outcome: Outcome,
+}
+
+impl Cache {
+    fn load() -> Cache {
+        let rustc_version = version(exercise::rustc());
+        let cache = fs::read_to_string(CACHE_FILE)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<Cache>(&contents).ok())
+            .filter(|cache| {
+                cache.rustc_version == rustc_version && cache.rustlings_version == RUSTLINGS_VERSION
+            });
+        cache.unwrap_or(Cache {
+            rustc_version,
+            rustlings_version: RUSTLINGS_VERSION.to_string(),
+            entries: HashMap::new(),
+        })
+    }
+
+    fn save(&self) -> io::Result<()> {
+        util::write_atomically(
+            Path::new(CACHE_FILE),
+            serde_json::to_vec(self).expect("Failed to serialize to JSON"),
+        )
+    }
+}
+
+fn cache() -> &'static Mutex<Cache> {
+    CACHE.get_or_init(|| Mutex::new(Cache::load()))
+}
+
+// The output of `rustc --version`
+fn version(mut rustc: Command) -> String {
+    rustc
+        .arg("--version")
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default()
+}
+
+// The version of the rustc of the exercise's own toolchain, if it has one.
+// Each toolchain is only asked once
+fn toolchain_version(exercise: &Exercise) -> String {
+    static VERSIONS: Mutex<BTreeMap<String, String>> = Mutex::new(BTreeMap::new());
+    let Some(toolchain) = exercise.active_toolchain() else {
+        return String::new();
+    };
+    let mut versions = VERSIONS.lock().unwrap_or_else(|e| e.into_inner());
+    versions
+        .entry(toolchain.to_string())
+        .or_insert_with(|| version(exercise.rustc()))
+        .clone()
+}
+
+// Everything that can influence the outcome of checking the exercise
+fn key(exercise: &Exercise) -> String {
+    let entry = serde_json::to_string(exercise).expect("Failed to serialize to JSON");
+    // Limits and colors may come from the configuration rather than the entry
+    let settings = serde_json::to_string(&(exercise.limits(), config::get().color()))
+        .expect("Failed to serialize to JSON");
+    let digest = Sha256::new()
+        .chain_update(exercise.source_hash())
+        .chain_update(entry)
+        .chain_update(settings)
+        .chain_update(toolchain_version(exercise))
+        .chain_update(contents(exercise.input()))
+        .chain_update(contents(exercise.expected_output()))
+        .finalize();
+    digest.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+// The contents of a file the exercise reads, or why it couldn't be read
+fn contents(file: io::Result<Option<String>>) -> String {
+    match file {
+        Ok(contents) => contents.unwrap_or_default(),
+        Err(e) => e.to_string(),
+    }
+}
+
+/// The outcome of the last check of the exercise, if it hasn't changed since.
+pub fn lookup(exercise: &Exercise) -> Option<Outcome> {
+    let key = key(exercise);
+    let cache = cache().lock().unwrap_or_else(|e| e.into_inner());
+    cache
+        .entries
+        .get(&exercise.name)
+        .filter(|entry| entry.key == key)
+        .map(|entry| entry.outcome.clone())
+}
+
+/// Remember the outcome of checking the exercise.
+pub fn store(exercise: &Exercise, outcome: &Outcome) {
+    let key = key(exercise);
+    let mut cache = cache().lock().unwrap_or_else(|e| e.into_inner());
+    cache.entries.insert(
+        exercise.name.clone(),
+        Entry {
+            key,
+            outcome: outcome.clone(),
+        },
+    );
+    // The cache is only an optimization, so failing to save it is fine
+    let _ = cache.save();
+}
@@ -0,0 +1,241 @@
+use crate::exercise::{ExerciseList, Mode, I_AM_DONE_REGEX};
+use crate::manifest;
+use crate::project::normalize;
+use glob::glob;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::PathBuf;
+
+// Something wrong with a manifest or an exercise, and where it is
+struct Problem {
+    file: PathBuf,
+    line: Option<usize>,
+    message: String,
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{line}: {}", self.file.display(), self.message),
+            None => write!(f, "{}: {}", self.file.display(), self.message),
+        }
+    }
+}
+
+// Check info.toml, the exercise packs and the exercises tree, and report
+// every problem found instead of stopping at the first one
+pub fn check_manifest(extra_packs: &[PathBuf]) -> Result<(), ()> {
+    let manifests = manifest::manifests(extra_packs).map_err(|e| println!("{e}"))?;
+    let marker = Regex::new(I_AM_DONE_REGEX).unwrap();
+    let errors_pattern = Regex::new(r"^E\d{4}$").unwrap();
+    let mut problems = Vec::new();
+    let mut defined_at: HashMap<String, (PathBuf, Option<usize>)> = HashMap::new();
+    let mut registered = HashSet::new();
+
+    for manifest in &manifests {
+        let problem = |line, message| Problem {
+            file: manifest.clone(),
+            line,
+            message,
+        };
+        let text = match fs::read_to_string(manifest) {
+            Ok(text) => text,
+            Err(e) => {
+                problems.push(problem(None, format!("Failed to read the manifest: {e}")));
+                continue;
+            }
+        };
+        let exercises = match toml::from_str::<ExerciseList>(&text) {
+            Ok(list) => list.exercises,
+            Err(e) => {
+                let line = e.span().map(|span| line_of_offset(&text, span.start));
+                problems.push(problem(
+                    line,
+                    format!(
+                        "Invalid manifest: {}",
+                        e.message().trim().replace('\n', " ")
+                    ),
+                ));
+                continue;
+            }
+        };
+
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+        for exercise in exercises {
+            let nth = occurrences.entry(exercise.name.clone()).or_default();
+            let line = line_of_name(&text, &exercise.name, *nth);
+            *nth += 1;
+            match defined_at.get(&exercise.name) {
+                Some((file, first)) => {
+                    let location = match first {
+                        Some(first) => format!("{}:{first}", file.display()),
+                        None => file.display().to_string(),
+                    };
+                    problems.push(problem(
+                        line,
+                        format!("`{}` is already defined at {location}", exercise.name),
+                    ));
+                }
+                None => {
+                    defined_at.insert(exercise.name.clone(), (manifest.clone(), line));
+                }
+            }
+            registered.insert(normalize(&exercise.path));
+
+            if let Some(solution) = &exercise.solution {
+                if !solution.is_file() {
+                    problems.push(problem(
+                        line,
+                        format!("The solution {} doesn't exist", solution.display()),
+                    ));
+                }
+            }
+            match exercise.mode {
+                Mode::CompileFail if exercise.expected_errors.is_empty() => {
+                    problems.push(problem(
+                        line,
+                        "A compile_fail exercise needs `expected_errors`".to_string(),
+                    ));
+                }
+                Mode::CompileFail => {
+                    for code in &exercise.expected_errors {
+                        if !errors_pattern.is_match(code) {
+                            problems.push(problem(
+                                line,
+                                format!("`{code}` isn't an error code like `E0382`"),
+                            ));
+                        }
+                    }
+                }
+                _ if !exercise.expected_errors.is_empty() => {
+                    problems.push(problem(
+                        line,
+                        "Only compile_fail exercises can have `expected_errors`".to_string(),
+                    ));
+                }
+                _ => {}
+            }
+            if exercise.edition.is_some() && matches!(exercise.mode, Mode::Cargo) {
+                problems.push(problem(
+                    line,
+                    "Cargo exercises take their edition from their Cargo.toml".to_string(),
+                ));
+            }
+            if exercise.clippy_lints.is_some() && !matches!(exercise.mode, Mode::Clippy) {
+                problems.push(problem(
+                    line,
+                    "Only clippy exercises can have `clippy_lints`".to_string(),
+                ));
+            }
+            if let Some(input) = &exercise.stdin_file {
+                if !input.is_file() {
+                    problems.push(problem(
+                        line,
+                        format!("The stdin file {} doesn't exist", input.display()),
+                    ));
+                }
+            }
+            if let Some(expected) = &exercise.expected_output_file {
+                if !expected.is_file() {
+                    problems.push(problem(
+                        line,
+                        format!("The expected output {} doesn't exist", expected.display()),
+                    ));
+                }
+            }
+            let expects_output =
+                exercise.expected_output.is_some() || exercise.expected_output_file.is_some();
+            if expects_output && !matches!(exercise.mode, Mode::Compile) {
+                problems.push(problem(
+                    line,
+                    "Only compile mode exercises can have an expected output".to_string(),
+                ));
+            }
+            if !exercise.path.exists() {
+                problems.push(problem(
+                    line,
+                    format!("The path {} doesn't exist", exercise.path.display()),
+                ));
+                continue;
+            }
+            if let Mode::Cargo = exercise.mode {
+                if !exercise.path.join("Cargo.toml").is_file() {
+                    problems.push(problem(
+                        line,
+                        format!(
+                            "{} is a cargo exercise without a Cargo.toml",
+                            exercise.path.display()
+                        ),
+                    ));
+                    continue;
+                }
+            }
+            // The marker of a Cargo exercise may be in any of its Rust files
+            let source: String = exercise
+                .rust_sources()
+                .iter()
+                .filter_map(|path| fs::read_to_string(path).ok())
+                .collect();
+            if let Mode::Test | Mode::Cargo = exercise.mode {
+                if !source.contains("#[test]") {
+                    problems.push(problem(
+                        line,
+                        format!(
+                            "{} is a {} exercise without any `#[test]`",
+                            exercise.path.display(),
+                            exercise.mode
+                        ),
+                    ));
+                }
+            }
+            if !marker.is_match(&source) {
+                problems.push(problem(
+                    line,
+                    format!("{} has no `I AM NOT DONE` marker", exercise.path.display()),
+                ));
+            }
+        }
+    }
+
+    for path in glob("exercises/**/*.rs").unwrap().flatten() {
+        // The files of a Cargo exercise are registered through its directory
+        let is_registered = path
+            .ancestors()
+            .any(|dir| registered.contains(&normalize(dir)));
+        if path.file_name().unwrap() == "mod.rs" || is_registered {
+            continue;
+        }
+        problems.push(Problem {
+            file: path,
+            line: None,
+            message: "Not registered in any manifest".to_string(),
+        });
+    }
+
+    if problems.is_empty() {
+        success!("Checked {} manifests, no problems found", manifests.len());
+        return Ok(());
+    }
+    for problem in &problems {
+        println!("{problem}");
+    }
+    warn!("Found {} problems", problems.len());
+    Err(())
+}
+
+// The 1-based line number of a byte offset
+fn line_of_offset(text: &str, offset: usize) -> usize {
+    text[..offset.min(text.len())].matches('\n').count() + 1
+}
+
+// The 1-based line number of the `nth` definition of the exercise's name
+fn line_of_name(text: &str, name: &str, nth: usize) -> Option<usize> {
+    let re = Regex::new(&format!(r#"^\s*name\s*=\s*"{}"\s*$"#, regex::escape(name))).unwrap();
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| re.is_match(line))
+        .nth(nth)
+        .map(|(i, _)| i + 1)
+}
@@ -0,0 +1,140 @@
+use crate::exercise::{self, Exercise, Mode};
+use crate::session;
+use regex::Regex;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+// What earlier versions of rustlings built into the tree
+const LEGACY_ARTIFACTS: &[&str] = &[
+    ".rustlings/target",
+    ".rustlings/clippy",
+    "exercises/clippy/Cargo.toml",
+    "exercises/clippy/Cargo.lock",
+    "exercises/clippy/target",
+];
+
+// The first bytes of ELF, Mach-O (both byte orders and universal) and PE executables
+const EXECUTABLE_MAGIC: &[&[u8]] = &[
+    b"\x7fELF",
+    b"\xcf\xfa\xed\xfe",
+    b"\xce\xfa\xed\xfe",
+    b"\xfe\xed\xfa\xcf",
+    b"\xfe\xed\xfa\xce",
+    b"\xca\xfe\xba\xbe",
+    b"MZ",
+];
+
+/// Remove the build artifacts that were left behind: binaries compiled
+/// next to the exercises, temporary files of older versions of rustlings
+/// and the session directories of runs that crashed or were killed.
+/// With `dry_run`, only list them.
+pub fn clean(exercises: &[Exercise], dry_run: bool) -> Result<(), ()> {
+    let artifacts = find_artifacts(exercises);
+    if artifacts.is_empty() {
+        success!("{}", "Nothing to clean up");
+        return Ok(());
+    }
+
+    let mut failed = false;
+    for artifact in &artifacts {
+        if dry_run {
+            println!("Would remove {}", artifact.display());
+            continue;
+        }
+        let removed = if artifact.is_dir() {
+            fs::remove_dir_all(artifact)
+        } else {
+            fs::remove_file(artifact)
+        };
+        match removed {
+            Ok(()) => println!("Removed {}", artifact.display()),
+            Err(e) => {
+                warn!(
+                    "Failed to remove {}",
+                    format!("{}: {e}", artifact.display())
+                );
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        return Err(());
+    }
+    if !dry_run {
+        success!("Removed {} stray artifacts", artifacts.len());
+    }
+    Ok(())
+}
+
+fn find_artifacts(exercises: &[Exercise]) -> Vec<PathBuf> {
+    let mut artifacts: Vec<PathBuf> = LEGACY_ARTIFACTS
+        .iter()
+        .map(PathBuf::from)
+        .filter(|path| path.exists())
+        .collect();
+
+    // Like `temp_6591_ThreadId1`, which older versions compiled to
+    let temp_file = Regex::new(r"^temp_\d+_ThreadId\d+(\.exe|\.pdb)?$").unwrap();
+    for entry in fs::read_dir(".").into_iter().flatten().flatten() {
+        if temp_file.is_match(&entry.file_name().to_string_lossy()) {
+            artifacts.push(PathBuf::from(entry.file_name()));
+        }
+    }
+
+    for dir in ["exercises", "solutions"] {
+        find_binaries(Path::new(dir), true, &mut artifacts);
+    }
+    // Exercise packs may keep their exercises elsewhere
+    for dir in exercise::pack_dirs(exercises) {
+        find_binaries(dir, false, &mut artifacts);
+    }
+
+    // What Cargo leaves in the packages of Cargo exercises
+    for exercise in exercises.iter().filter(|e| matches!(e.mode, Mode::Cargo)) {
+        for artifact in ["Cargo.lock", "target"] {
+            let path = exercise.path.join(artifact);
+            if path.exists() {
+                artifacts.push(path);
+            }
+        }
+    }
+
+    artifacts.extend(session::stale_dirs());
+    artifacts.sort();
+    artifacts.dedup();
+    artifacts
+}
+
+// Binaries compiled from the Rust files next to them, like
+// `intro1` or `intro1.exe` and `intro1.pdb` next to `intro1.rs`
+fn find_binaries(dir: &Path, recursive: bool, artifacts: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if recursive {
+                find_binaries(&path, recursive, artifacts);
+            }
+            continue;
+        }
+        if !path.with_extension("rs").is_file() {
+            continue;
+        }
+        let is_binary = match path.extension().and_then(|e| e.to_str()) {
+            None | Some("exe") => is_executable(&path).unwrap_or(false),
+            Some("pdb") => true,
+            Some(_) => false,
+        };
+        if is_binary {
+            artifacts.push(path);
+        }
+    }
+}
+
+fn is_executable(path: &Path) -> io::Result<bool> {
+    let mut magic = [0; 4];
+    let read = File::open(path)?.read(&mut magic)?;
+    Ok(EXECUTABLE_MAGIC
+        .iter()
+        .any(|expected| magic[..read].starts_with(expected)))
+}
@@ -0,0 +1,271 @@
+use crate::limits;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// The configuration file of the rustlings directory
+pub const PROJECT_CONFIG: &str = "rustlings.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+// When to color the output
+#[derive(Deserialize, Serialize, Copy, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Color {
+    // Color the output of terminals that support it
+    Auto,
+    Always,
+    Never,
+}
+
+// The settings of a single source, such as a configuration file.
+// Settings a source leaves out come from the sources below it
+#[derive(Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    // Show the output of test exercises, like `--nocapture`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbose: Option<bool>,
+    // Show hints on success in watch mode, like `watch --success-hints`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success_hints: Option<bool>,
+    // Use emoji in messages, unless NO_EMOJI is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+    // Clear the terminal before watch mode checks the exercises again
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clear_screen: Option<bool>,
+    // The command to open exercises with, like "code --wait"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+    // How many seconds exercises may run, unless info.toml says otherwise
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    // How many MiB of memory exercises may map, unless info.toml says otherwise.
+    // Unlimited unless set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_limit: Option<u64>,
+    // How many seconds of CPU time exercises may use, unless info.toml says otherwise
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_time_limit: Option<u64>,
+}
+
+impl Settings {
+    fn defaults() -> Settings {
+        Settings {
+            verbose: Some(false),
+            success_hints: Some(false),
+            emoji: Some(true),
+            color: Some(Color::Auto),
+            clear_screen: Some(true),
+            editor: None,
+            timeout: Some(limits::DEFAULT_TIMEOUT.as_secs()),
+            memory_limit: None,
+            cpu_time_limit: Some(limits::DEFAULT_CPU_TIME_LIMIT),
+        }
+    }
+
+    // The settings the environment variables rustlings
+    // has always supported stand for
+    fn from_env() -> Settings {
+        let mut settings = Settings::default();
+        if env::var_os("NO_EMOJI").is_some() {
+            settings.emoji = Some(false);
+        }
+        if env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            settings.color = Some(Color::Never);
+        } else if env::var_os("CLICOLOR_FORCE").is_some_and(|v| v != "0") {
+            settings.color = Some(Color::Always);
+        }
+        settings
+    }
+}
+
+// Where a layer of settings comes from
+enum Source {
+    Flags,
+    Env,
+    File(PathBuf),
+    // An environment variable other programs read as well, like `EDITOR`
+    Var(&'static str),
+    Default,
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Source::Flags => write!(f, "command line"),
+            Source::Env => write!(f, "environment"),
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Var(name) => write!(f, "{name}"),
+            Source::Default => write!(f, "default"),
+        }
+    }
+}
+
+/// The effective configuration. Each setting comes from the first of
+/// the command line flags, the environment variables, `rustlings.toml`,
+/// the user's `config.toml`, `VISUAL` or `EDITOR` and the defaults that has it.
+pub struct Config {
+    layers: Vec<(Source, Settings)>,
+}
+
+impl Config {
+    fn load(flags: Settings) -> Result<Config, String> {
+        let mut layers = vec![(Source::Flags, flags), (Source::Env, Settings::from_env())];
+        let files = [Some(PathBuf::from(PROJECT_CONFIG)), user_config()];
+        for path in files.into_iter().flatten() {
+            let text = match fs::read_to_string(&path) {
+                Ok(text) => text,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(format!("Failed to read {}: {e}", path.display())),
+            };
+            let settings = toml::from_str(&text)
+                .map_err(|e| format!("Failed to parse {}: {e}", path.display()))?;
+            layers.push((Source::File(path), settings));
+        }
+        // The editor every program uses is only the fallback
+        // for the one rustlings is configured to use
+        if let Some((name, editor)) = ["VISUAL", "EDITOR"]
+            .into_iter()
+            .find_map(|name| Some((name, env::var(name).ok().filter(|e| !e.trim().is_empty())?)))
+        {
+            let settings = Settings {
+                editor: Some(editor),
+                ..Settings::default()
+            };
+            layers.push((Source::Var(name), settings));
+        }
+        layers.push((Source::Default, Settings::defaults()));
+        Ok(Config { layers })
+    }
+
+    // The first value of the setting, with where it comes from
+    fn setting<T>(&self, get: impl Fn(&Settings) -> Option<T>) -> Option<(T, &Source)> {
+        self.layers
+            .iter()
+            .find_map(|(source, settings)| get(settings).map(|value| (value, source)))
+    }
+
+    // The value of a setting, which the defaults have if nothing else does
+    fn value<T>(&self, get: impl Fn(&Settings) -> Option<T>) -> T {
+        self.setting(get)
+            .map(|(value, _)| value)
+            .expect("The setting has no default")
+    }
+
+    pub fn verbose(&self) -> bool {
+        self.value(|s| s.verbose)
+    }
+
+    pub fn success_hints(&self) -> bool {
+        self.value(|s| s.success_hints)
+    }
+
+    pub fn emoji(&self) -> bool {
+        self.value(|s| s.emoji)
+    }
+
+    pub fn color(&self) -> Color {
+        self.value(|s| s.color)
+    }
+
+    pub fn clear_screen(&self) -> bool {
+        self.value(|s| s.clear_screen)
+    }
+
+    pub fn editor(&self) -> Option<String> {
+        self.setting(|s| s.editor.clone()).map(|(editor, _)| editor)
+    }
+
+    pub fn timeout(&self) -> u64 {
+        self.value(|s| s.timeout)
+    }
+
+    pub fn memory_limit(&self) -> Option<u64> {
+        self.setting(|s| s.memory_limit).map(|(limit, _)| limit)
+    }
+
+    pub fn cpu_time_limit(&self) -> u64 {
+        self.value(|s| s.cpu_time_limit)
+    }
+}
+
+/// Load the configuration, with the settings of the command line flags
+/// taking precedence, and make it available through `get`.
+pub fn init(flags: Settings) -> Result<&'static Config, String> {
+    let config = Config::load(flags)?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+/// The configuration `init` loaded, or the defaults until it's called
+pub fn get() -> &'static Config {
+    static DEFAULTS: OnceLock<Config> = OnceLock::new();
+    CONFIG.get().unwrap_or_else(|| {
+        DEFAULTS.get_or_init(|| Config {
+            layers: vec![(Source::Default, Settings::defaults())],
+        })
+    })
+}
+
+// `$XDG_CONFIG_HOME/rustlings/config.toml`, or `~/.config/rustlings/config.toml`
+fn user_config() -> Option<PathBuf> {
+    let dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home::home_dir().map(|home| home.join(".config")))?;
+    Some(dir.join("rustlings").join("config.toml"))
+}
+
+// Print every setting with its effective value and where it comes from
+pub fn show() -> Result<(), ()> {
+    let config = get();
+    let settings = [
+        (
+            "verbose",
+            config.setting(|s| s.verbose.map(toml::Value::from)),
+        ),
+        (
+            "success_hints",
+            config.setting(|s| s.success_hints.map(toml::Value::from)),
+        ),
+        ("emoji", config.setting(|s| s.emoji.map(toml::Value::from))),
+        (
+            "color",
+            config.setting(|s| s.color.and_then(|c| toml::Value::try_from(c).ok())),
+        ),
+        (
+            "clear_screen",
+            config.setting(|s| s.clear_screen.map(toml::Value::from)),
+        ),
+        (
+            "editor",
+            config.setting(|s| s.editor.clone().map(toml::Value::from)),
+        ),
+        ("timeout", config.setting(|s| s.timeout.map(to_integer))),
+        (
+            "memory_limit",
+            config.setting(|s| s.memory_limit.map(to_integer)),
+        ),
+        (
+            "cpu_time_limit",
+            config.setting(|s| s.cpu_time_limit.map(to_integer)),
+        ),
+    ];
+    for (name, setting) in settings {
+        match setting {
+            Some((value, source)) => println!("{:<32}# {source}", format!("{name} = {value}")),
+            None => println!("# {name} isn't set"),
+        }
+    }
+    Ok(())
+}
+
+fn to_integer(n: u64) -> toml::Value {
+    toml::Value::Integer(n.try_into().unwrap_or(i64::MAX))
+}
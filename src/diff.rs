@@ -0,0 +1,51 @@
+use console::style;
+use similar::{ChangeTag, TextDiff};
+
+// Print a colored unified diff between two texts,
+// labeling the sides with the given names
+pub fn print_unified(old_name: &str, old: &str, new_name: &str, new: &str) {
+    let diff = TextDiff::from_lines(old, new);
+    println!("{}", style(format!("--- {old_name}")).red().bold());
+    println!("{}", style(format!("+++ {new_name}")).green().bold());
+    for group in diff.grouped_ops(3) {
+        let (first, last) = (&group[0], &group[group.len() - 1]);
+        let old_range = first.old_range().start..last.old_range().end;
+        let new_range = first.new_range().start..last.new_range().end;
+        println!(
+            "{}",
+            style(format!(
+                "@@ -{},{} +{},{} @@",
+                old_range.start + 1,
+                old_range.len(),
+                new_range.start + 1,
+                new_range.len()
+            ))
+            .cyan()
+        );
+        for op in &group {
+            for change in diff.iter_changes(op) {
+                let line = change.to_string_lossy();
+                let line = line.trim_end_matches('\n');
+                match change.tag() {
+                    ChangeTag::Delete => println!("{}", style(format!("-{line}")).red()),
+                    ChangeTag::Insert => println!("{}", style(format!("+{line}")).green()),
+                    ChangeTag::Equal => println!(" {line}"),
+                }
+            }
+        }
+    }
+}
+
+// Print a diff some other tool already produced,
+// coloring its removed and added lines
+pub fn print_colored(diff: &str) {
+    for line in diff.lines() {
+        if line.starts_with('-') {
+            println!("{}", style(line).red());
+        } else if line.starts_with('+') {
+            println!("{}", style(line).green());
+        } else {
+            println!("{line}");
+        }
+    }
+}
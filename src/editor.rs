@@ -0,0 +1,155 @@
+use crate::config;
+use crate::exercise::{Exercise, Mode, I_AM_DONE_REGEX};
+use crate::util;
+use regex::Regex;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Editors that take the line to open a file at as `+<line> <file>`
+const PLUS_LINE: &[&str] = &[
+    "vi",
+    "vim",
+    "nvim",
+    "gvim",
+    "mvim",
+    "view",
+    "nano",
+    "pico",
+    "emacs",
+    "emacsclient",
+    "micro",
+    "kak",
+    "joe",
+    "mg",
+    "ne",
+];
+// Editors that take it as `--goto <file>:<line>`
+const GOTO: &[&str] = &["code", "code-insiders", "codium", "cursor"];
+// Editors that take it as `<file>:<line>`
+const FILE_LINE: &[&str] = &["subl", "zed", "hx", "helix"];
+
+/// Open the exercise in the configured editor, at the first compiler error
+/// or else at its `I AM NOT DONE` comment, and wait for the editor to exit.
+pub fn edit(exercise: &Exercise) -> Result<(), ()> {
+    let Some(editor) = config::get().editor() else {
+        let message = format!(
+            "There's no editor to open {} with. Set `editor` in rustlings.toml, \
+             or the VISUAL or EDITOR environment variable",
+            exercise.name
+        );
+        warn!("{}", message);
+        return Err(());
+    };
+    // Split like a shell would, so that paths can have spaces in them when quoted
+    let words = match shell_words::split(&editor) {
+        Ok(words) if !words.is_empty() => words,
+        Ok(_) => {
+            warn!("The editor setting {}", "is empty");
+            return Err(());
+        }
+        Err(e) => {
+            warn!("Failed to parse the editor {}", format!("`{editor}`: {e}"));
+            return Err(());
+        }
+    };
+    let (program, args) = words.split_first().expect("There is at least one word");
+
+    let (file, line) = position(exercise);
+    let mut cmd = Command::new(program);
+    cmd.args(args);
+    match line {
+        Some(line) => cmd.args(position_args(program, &file, line)),
+        None => cmd.arg(&file),
+    };
+    match cmd.status() {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => {
+            warn!("The editor exited with {}", status);
+            Err(())
+        }
+        Err(e) => {
+            warn!("Failed to start the editor {}", format!("`{editor}`: {e}"));
+            Err(())
+        }
+    }
+}
+
+// The arguments that open the file at the line in the given editor.
+// Editors that aren't known get only the file
+fn position_args(program: &str, file: &Path, line: usize) -> Vec<OsString> {
+    let name = Path::new(program)
+        .file_stem()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let mut file_line = file.as_os_str().to_owned();
+    file_line.push(format!(":{line}"));
+    if PLUS_LINE.contains(&name.as_str()) {
+        vec![format!("+{line}").into(), file.into()]
+    } else if GOTO.contains(&name.as_str()) {
+        vec!["--goto".into(), file_line]
+    } else if FILE_LINE.contains(&name.as_str()) {
+        vec![file_line]
+    } else {
+        vec![file.into()]
+    }
+}
+
+// Where to open the exercise: the first compiler error in one of its files,
+// its `I AM NOT DONE` comment, or the start of its main file
+fn position(exercise: &Exercise) -> (PathBuf, Option<usize>) {
+    if !matches!(exercise.mode, Mode::CompileFail) {
+        if let Err(output) = exercise.compile() {
+            if let Some(error) = first_error(exercise, &output.stderr) {
+                return error;
+            }
+        }
+    }
+    let marker = Regex::new(I_AM_DONE_REGEX).unwrap();
+    for path in exercise.rust_sources() {
+        let Ok(source) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if let Some(index) = source.lines().position(|line| marker.is_match(line)) {
+            return (path, Some(index + 1));
+        }
+    }
+    (main_file(exercise), None)
+}
+
+// The file and line of the first `--> file:line:column` in the compiler
+// output that points into the exercise, rather than into the standard library
+fn first_error(exercise: &Exercise, stderr: &str) -> Option<(PathBuf, Option<usize>)> {
+    let location = Regex::new(r"(?m)^\s*--> (.+):(\d+):\d+$").unwrap();
+    let stderr = util::strip_colors(stderr);
+    let sources: Vec<PathBuf> = exercise
+        .sources()
+        .iter()
+        .filter_map(|source| source.canonicalize().ok())
+        .collect();
+    let error = location.captures_iter(&stderr).find_map(|captures| {
+        let path = Path::new(&captures[1]);
+        // Cargo reports paths relative to the package
+        let candidates = [exercise.path.join(path), path.to_path_buf()];
+        let file = candidates.into_iter().find(|candidate| {
+            candidate
+                .canonicalize()
+                .is_ok_and(|candidate| sources.contains(&candidate))
+        })?;
+        Some((file, captures[2].parse().ok()))
+    });
+    error
+}
+
+// The file to open a Cargo exercise at when there's no line to go to
+fn main_file(exercise: &Exercise) -> PathBuf {
+    if !exercise.path.is_dir() {
+        return exercise.path.clone();
+    }
+    ["src/lib.rs", "src/main.rs"]
+        .into_iter()
+        .map(|file| exercise.path.join(file))
+        .find(|file| file.is_file())
+        .unwrap_or_else(|| exercise.path.clone())
+}
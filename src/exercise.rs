@@ -1,41 +1,128 @@
+use crate::config::{self, Color};
+use crate::limits::{self, Exit, Limits, Resource};
+use crate::session;
+use crate::util;
+use clap::ValueEnum;
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::env;
 use std::fmt::{self, Display, Formatter};
 use std::fs::{self, remove_file, File};
-use std::io::Read;
-use std::path::PathBuf;
-use std::process::{self, Command};
-
-const RUSTC_COLOR_ARGS: &[&str] = &["--color", "always"];
-const RUSTC_EDITION_ARGS: &[&str] = &["--edition", "2021"];
-const I_AM_DONE_REGEX: &str = r"(?m)^\s*///?\s*I\s+AM\s+NOT\s+DONE";
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, UNIX_EPOCH};
+
+// The compiler output is colored unless color is turned off,
+// even when it's captured
+fn rustc_color_args() -> [&'static str; 2] {
+    match config::get().color() {
+        Color::Never => ["--color", "never"],
+        _ => ["--color", "always"],
+    }
+}
+/// The environment variable naming the rustc to use instead of
+/// the one rustup picks, which `--rustc` sets as well
+pub const RUSTC_ENV: &str = "RUSTLINGS_RUSTC";
+pub const I_AM_DONE_REGEX: &str = r"(?m)^\s*///?\s*I\s+AM\s+NOT\s+DONE";
 const CONTEXT: usize = 2;
-const CLIPPY_CARGO_TOML_PATH: &str = "./exercises/clippy/Cargo.toml";
 
-// Get a temporary file name that is hopefully unique
+// Get a temporary file name in the session directory
+// that is unique to the current thread
 #[inline]
-fn temp_file() -> String {
+fn temp_file() -> PathBuf {
     let thread_id: String = format!("{:?}", std::thread::current().id())
         .chars()
         .filter(|c| c.is_alphanumeric())
         .collect();
 
-    format!("./temp_{}_{thread_id}", process::id())
+    session::dir().join(format!("temp_{thread_id}"))
 }
 
 // The mode of the exercise.
-#[derive(Deserialize, Copy, Clone, Debug)]
+#[derive(Deserialize, Serialize, ValueEnum, Copy, Clone, Debug, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum Mode {
     // Indicates that the exercise should be compiled as a binary
+    #[default]
     Compile,
     // Indicates that the exercise should be compiled as a test harness
     Test,
     // Indicates that the exercise should be linted with clippy
     Clippy,
+    // Indicates that the exercise should fail to compile
+    // with one of its expected errors
+    #[serde(rename = "compile_fail")]
+    #[value(name = "compile_fail")]
+    CompileFail,
+    // Indicates that the exercise is a Cargo package, which should
+    // build and pass its tests
+    Cargo,
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let name = match self {
+            Mode::Compile => "compile",
+            Mode::Test => "test",
+            Mode::Clippy => "clippy",
+            Mode::CompileFail => "compile_fail",
+            Mode::Cargo => "cargo",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// The Rust edition an exercise is compiled with
+#[derive(Deserialize, Serialize, Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Edition {
+    #[serde(rename = "2015")]
+    E2015,
+    #[serde(rename = "2018")]
+    E2018,
+    #[default]
+    #[serde(rename = "2021")]
+    E2021,
+    #[serde(rename = "2024")]
+    E2024,
+}
+
+impl Display for Edition {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let name = match self {
+            Edition::E2015 => "2015",
+            Edition::E2018 => "2018",
+            Edition::E2021 => "2021",
+            Edition::E2024 => "2024",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// How hard an exercise is
+#[derive(Deserialize, Serialize, ValueEnum, Copy, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        };
+        write!(f, "{name}")
+    }
 }
 
+// The contents of info.toml or of an exercise pack
 #[derive(Deserialize)]
 pub struct ExerciseList {
     pub exercises: Vec<Exercise>,
@@ -43,16 +130,108 @@ pub struct ExerciseList {
 
 // A representation of a rustlings exercise.
 // This is deserialized from the accompanying info.toml file
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
 pub struct Exercise {
     // Name of the exercise
     pub name: String,
-    // The path to the file containing the exercise's source code
+    // The path to the file containing the exercise's source code,
+    // or to the package directory of a Cargo exercise
     pub path: PathBuf,
-    // The mode of the exercise (Test, Compile, or Clippy)
+    // The mode of the exercise (Test, Compile, Clippy, CompileFail or Cargo)
     pub mode: Mode,
-    // The hint text associated with the exercise
-    pub hint: String,
+    // The hints associated with the exercise, from the vaguest to the most
+    // specific. In info.toml, this is either a `hints` list or a single `hint`
+    #[serde(alias = "hint", deserialize_with = "one_or_many")]
+    pub hints: Vec<String>,
+    // How hard the exercise is
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<Difficulty>,
+    // Free-form labels to build practice sets from, like "quiz"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    // The sections of the Rust book the exercise is about, like "4.1-2"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub book_chapter: Option<String>,
+    // The Rust concepts the exercise practices, like "ownership"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub topics: Vec<String>,
+    // The edition to compile the exercise with, 2021 unless given.
+    // Cargo exercises take it from their Cargo.toml instead
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edition: Option<Edition>,
+    // Extra flags to pass to rustc, like `["-C", "overflow-checks=off"]`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rustc_args: Vec<String>,
+    // The rustup toolchain to build the exercise with, like "nightly"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toolchain: Option<String>,
+    // The lints a clippy exercise denies or allows on top of `-D warnings`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clippy_lints: Option<ClippyLints>,
+    // Whether the exercise only passes once rustfmt has nothing to change
+    #[serde(default, skip_serializing_if = "util::is_false")]
+    pub require_fmt: bool,
+    // The error codes a compile_fail exercise may fail with, like "E0382"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expected_errors: Vec<String>,
+    // The input to feed the binary or test harness on stdin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdin: Option<String>,
+    // A file holding the input to feed on stdin, for longer inputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdin_file: Option<PathBuf>,
+    // What a compile mode exercise has to print to stdout to pass
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_output: Option<String>,
+    // A file holding what the exercise has to print, for longer outputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_output_file: Option<PathBuf>,
+    // Whether to ignore trailing whitespace and trailing empty lines
+    // when comparing the output with the expected one
+    #[serde(default, skip_serializing_if = "util::is_false")]
+    pub normalize_whitespace: bool,
+    // How many seconds the binary or test harness may run before it's killed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    // How many MiB of memory the binary or test harness may map
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_limit: Option<u64>,
+    // How many seconds of CPU time the binary or test harness may use
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_time_limit: Option<u64>,
+    // How many processes and threads may run as the learner's user at once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process_limit: Option<u64>,
+    // The path to the reference solution, if it isn't in the default place
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solution: Option<PathBuf>,
+    // The info.toml or pack file the exercise was loaded from
+    #[serde(skip)]
+    pub manifest: PathBuf,
+}
+
+// The lints to pass to clippy, like "clippy::float_cmp"
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct ClippyLints {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+}
+
+// Accept either a single string or a list of strings
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(one) => vec![one],
+        OneOrMany::Many(many) => many,
+    })
 }
 
 // An enum to track of the state of an Exercise.
@@ -79,18 +258,20 @@ pub struct ContextLine {
 // The result of compiling an exercise
 pub struct CompiledExercise<'a> {
     exercise: &'a Exercise,
+    // The binaries or test harnesses to run, in order
+    executables: Vec<PathBuf>,
     _handle: FileHandle,
 }
 
 impl<'a> CompiledExercise<'a> {
     // Run the compiled exercise
-    pub fn run(&self) -> Result<ExerciseOutput, ExerciseOutput> {
-        self.exercise.run()
+    pub fn run(&self) -> Result<ExerciseOutput, RunFailure> {
+        self.exercise.run(&self.executables)
     }
 }
 
 // A representation of an already executed binary
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct ExerciseOutput {
     // The textual contents of the standard output of the binary
     pub stdout: String,
@@ -98,6 +279,19 @@ pub struct ExerciseOutput {
     pub stderr: String,
 }
 
+// Why running a compiled exercise failed
+#[derive(Debug)]
+pub enum RunFailure {
+    // The binary or test harness exited with an error
+    Failed(ExerciseOutput),
+    // The binary or test harness ran out of time and was killed
+    TimedOut(ExerciseOutput),
+    // The binary or test harness used up its share of a resource
+    LimitExceeded(Resource, ExerciseOutput),
+    // The binary ran successfully, but didn't print the expected output
+    WrongOutput(ExerciseOutput),
+}
+
 struct FileHandle;
 
 impl Drop for FileHandle {
@@ -107,58 +301,56 @@ impl Drop for FileHandle {
 }
 
 impl Exercise {
-    pub fn compile(&self) -> Result<CompiledExercise, ExerciseOutput> {
+    pub fn compile(&self) -> Result<CompiledExercise<'_>, ExerciseOutput> {
+        if let Mode::Cargo = self.mode {
+            return self.compile_package();
+        }
         let cmd = match self.mode {
-            Mode::Compile => Command::new("rustc")
-                .args([self.path.to_str().unwrap(), "-o", &temp_file()])
-                .args(RUSTC_COLOR_ARGS)
-                .args(RUSTC_EDITION_ARGS)
+            Mode::Compile | Mode::CompileFail => self
+                .rustc()
+                .arg(&self.path)
+                .arg("-o")
+                .arg(temp_file())
+                .args(rustc_color_args())
+                .args(self.rustc_flags())
                 .output(),
-            Mode::Test => Command::new("rustc")
-                .args(["--test", self.path.to_str().unwrap(), "-o", &temp_file()])
-                .args(RUSTC_COLOR_ARGS)
-                .args(RUSTC_EDITION_ARGS)
+            Mode::Test => self
+                .rustc()
+                .arg("--test")
+                .arg(&self.path)
+                .arg("-o")
+                .arg(temp_file())
+                .args(rustc_color_args())
+                .args(self.rustc_flags())
                 .output(),
+            Mode::Cargo => unreachable!("Cargo exercises are built by compile_package"),
             Mode::Clippy => {
-                let cargo_toml = format!(
-                    r#"[package]
-name = "{}"
-version = "0.0.1"
-edition = "2021"
-[[bin]]
-name = "{}"
-path = "{}.rs""#,
-                    self.name, self.name, self.name
-                );
-                let cargo_toml_error_msg = if env::var("NO_EMOJI").is_ok() {
+                let cargo_toml_error_msg = if !config::get().emoji() {
                     "Failed to write Clippy Cargo.toml file."
                 } else {
                     "Failed to write 📎 Clippy 📎 Cargo.toml file."
                 };
-                fs::write(CLIPPY_CARGO_TOML_PATH, cargo_toml).expect(cargo_toml_error_msg);
+                let cargo_toml = self.write_clippy_manifest().expect(cargo_toml_error_msg);
                 // To support the ability to run the clippy exercises, build
                 // an executable, in addition to running clippy. With a
                 // compilation failure, this would silently fail. But we expect
                 // clippy to reflect the same failure while compiling later.
-                Command::new("rustc")
-                    .args([self.path.to_str().unwrap(), "-o", &temp_file()])
-                    .args(RUSTC_COLOR_ARGS)
-                    .args(RUSTC_EDITION_ARGS)
+                self.rustc()
+                    .arg(&self.path)
+                    .arg("-o")
+                    .arg(temp_file())
+                    .args(rustc_color_args())
+                    .args(self.rustc_flags())
                     .output()
                     .expect("Failed to compile!");
-                // Due to an issue with Clippy, a cargo clean is required to catch all lints.
-                // See https://github.com/rust-lang/rust-clippy/issues/2604
-                // This is already fixed on Clippy's master branch. See this issue to track merging into Cargo:
-                // https://github.com/rust-lang/rust-clippy/issues/3837
-                Command::new("cargo")
-                    .args(["clean", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
-                    .args(RUSTC_COLOR_ARGS)
-                    .output()
-                    .expect("Failed to run 'cargo clean'");
-                Command::new("cargo")
-                    .args(["clippy", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
-                    .args(RUSTC_COLOR_ARGS)
-                    .args(["--", "-D", "warnings", "-D", "clippy::float_cmp"])
+                self.cargo()
+                    .args(["clippy", "--offline", "--manifest-path"])
+                    .arg(cargo_toml)
+                    .arg("--target-dir")
+                    .arg(self.target_dir())
+                    .args(rustc_color_args())
+                    .arg("--")
+                    .args(self.clippy_args())
                     .output()
             }
         }
@@ -167,6 +359,7 @@ path = "{}.rs""#,
         if cmd.status.success() {
             Ok(CompiledExercise {
                 exercise: self,
+                executables: vec![temp_file()],
                 _handle: FileHandle,
             })
         } else {
@@ -178,45 +371,316 @@ path = "{}.rs""#,
         }
     }
 
-    fn run(&self) -> Result<ExerciseOutput, ExerciseOutput> {
+    // Build the package of a Cargo exercise and its test harnesses,
+    // in a target directory of its own so packages can't interfere
+    fn compile_package(&self) -> Result<CompiledExercise<'_>, ExerciseOutput> {
+        let cmd = self
+            .cargo()
+            .args(["test", "--no-run", "--offline", "--manifest-path"])
+            .arg(self.path.join("Cargo.toml"))
+            .arg("--target-dir")
+            .arg(self.target_dir())
+            .args(["--message-format", "json-diagnostic-rendered-ansi"])
+            .args(rustc_color_args())
+            .output()
+            .expect("Failed to run 'cargo test --no-run'");
+
+        // Cargo reports one JSON message per line: the rendered
+        // diagnostics are what a failed build shows, and the test
+        // harnesses are what a successful one runs
+        let mut diagnostics = String::new();
+        let mut executables = Vec::new();
+        for line in String::from_utf8_lossy(&cmd.stdout).lines() {
+            let Ok(message) = serde_json::from_str::<serde_json::Value>(line) else {
+                continue;
+            };
+            match message["reason"].as_str() {
+                Some("compiler-message") => {
+                    if let Some(rendered) = message["message"]["rendered"].as_str() {
+                        diagnostics.push_str(rendered);
+                    }
+                }
+                Some("compiler-artifact") if message["profile"]["test"] == true => {
+                    if let Some(executable) = message["executable"].as_str() {
+                        executables.push(PathBuf::from(executable));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if cmd.status.success() {
+            Ok(CompiledExercise {
+                exercise: self,
+                executables,
+                _handle: FileHandle,
+            })
+        } else {
+            Err(ExerciseOutput {
+                stdout: String::new(),
+                stderr: diagnostics + &String::from_utf8_lossy(&cmd.stderr),
+            })
+        }
+    }
+
+    // The edition the exercise is compiled with
+    pub fn edition(&self) -> Edition {
+        self.edition.unwrap_or_default()
+    }
+
+    // rustc, as picked by `--rustc`, or by rustup for the exercise's toolchain.
+    // A rustc given with `--rustc` takes precedence over the toolchain
+    pub fn rustc(&self) -> Command {
+        match self.active_toolchain() {
+            Some(toolchain) => {
+                let mut command = Command::new("rustc");
+                command.arg(format!("+{toolchain}"));
+                command
+            }
+            None => rustc(),
+        }
+    }
+
+    // The edition and the exercise's own flags, for rustc
+    fn rustc_flags(&self) -> Vec<String> {
+        let mut flags = vec!["--edition".to_string(), self.edition().to_string()];
+        flags.extend(self.rustc_args.iter().cloned());
+        flags
+    }
+
+    // The rustup toolchain the exercise is built with, unless a rustc
+    // given with `--rustc` takes precedence over it
+    pub fn active_toolchain(&self) -> Option<&str> {
+        match env::var_os(RUSTC_ENV) {
+            Some(_) => None,
+            None => self.toolchain.as_deref(),
+        }
+    }
+
+    // Cargo for the exercise's toolchain, or building with the rustc
+    // given with `--rustc`, and with the exercise's rustc flags
+    fn cargo(&self) -> Command {
+        let mut command = Command::new("cargo");
+        if let Some(toolchain) = self.active_toolchain() {
+            command.arg(format!("+{toolchain}"));
+        }
+        if let Some(rustc) = env::var_os(RUSTC_ENV) {
+            command.env("RUSTC", rustc);
+        }
+        if !self.rustc_args.is_empty() {
+            // Unlike RUSTFLAGS, this keeps flags with spaces in them intact
+            command.env("CARGO_ENCODED_RUSTFLAGS", self.rustc_args.join("\x1f"));
+        }
+        command
+    }
+
+    // Generate a package around a clippy exercise, so that it can be linted
+    // without touching anything next to it, and return its manifest.
+    // Each exercise has a package and a target directory of its own,
+    // so clippy exercises can be checked concurrently
+    fn write_clippy_manifest(&self) -> io::Result<PathBuf> {
+        let dir = session::dir().join("clippy").join(&self.name);
+        fs::create_dir_all(&dir)?;
+        let path = self.path.canonicalize()?;
+        let cargo_toml = format!(
+            r#"[package]
+name = "{}"
+version = "0.0.1"
+edition = "{}"
+publish = false
+
+[[bin]]
+name = "{}"
+path = {}
+
+# Keep the package out of any workspace around it
+[workspace]
+"#,
+            self.name,
+            self.edition(),
+            self.name,
+            toml::Value::String(path.to_string_lossy().to_string())
+        );
+        let manifest = dir.join("Cargo.toml");
+        fs::write(&manifest, cargo_toml)?;
+        Ok(manifest)
+    }
+
+    // The flags clippy lints the exercise with: every warning is an error,
+    // along with the lints the exercise denies
+    fn clippy_args(&self) -> Vec<String> {
+        let mut args = vec!["-D".to_string(), "warnings".to_string()];
+        if let Some(lints) = &self.clippy_lints {
+            for lint in &lints.deny {
+                args.extend(["-D".to_string(), lint.clone()]);
+            }
+            for lint in &lints.allow {
+                args.extend(["-A".to_string(), lint.clone()]);
+            }
+        }
+        args
+    }
+
+    // Where Cargo and clippy exercises are built, in the session directory
+    fn target_dir(&self) -> PathBuf {
+        session::dir().join("target").join(&self.name)
+    }
+
+    fn run(&self, executables: &[PathBuf]) -> Result<ExerciseOutput, RunFailure> {
         let arg = match self.mode {
-            Mode::Test => "--show-output",
+            Mode::Test | Mode::Cargo => "--show-output",
             _ => "",
         };
-        let cmd = Command::new(temp_file())
-            .arg(arg)
-            .output()
+        let mut output = ExerciseOutput {
+            stdout: String::new(),
+            stderr: String::new(),
+        };
+        let input = match self.input() {
+            Ok(input) => input,
+            Err(e) => {
+                output.stdout = format!("The input of {} is missing. {e}", self.name);
+                return Err(RunFailure::Failed(output));
+            }
+        };
+
+        // A Cargo package may have several test harnesses,
+        // which have to pass one after the other
+        for executable in executables {
+            let mut command = Command::new(executable);
+            command.arg(arg);
+            if let Mode::Cargo = self.mode {
+                // Like `cargo test`, run the tests from the package directory
+                command.current_dir(&self.path);
+            }
+            let cmd = limits::output(
+                &mut command,
+                input.as_ref().map(String::as_bytes),
+                &self.limits(),
+            )
             .expect("Failed to run 'run' command");
 
-        let output = ExerciseOutput {
-            stdout: String::from_utf8_lossy(&cmd.stdout).to_string(),
-            stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
-        };
+            output
+                .stdout
+                .push_str(&String::from_utf8_lossy(&cmd.stdout));
+            output
+                .stderr
+                .push_str(&String::from_utf8_lossy(&cmd.stderr));
+
+            match cmd.exit {
+                Exit::Status(status) if status.success() => {}
+                Exit::Status(_) => return Err(RunFailure::Failed(output)),
+                Exit::TimedOut => return Err(RunFailure::TimedOut(output)),
+                Exit::LimitExceeded(resource) => {
+                    return Err(RunFailure::LimitExceeded(resource, output))
+                }
+            }
+        }
 
-        if cmd.status.success() {
-            Ok(output)
-        } else {
-            Err(output)
+        match self.expected_output() {
+            Ok(Some(expected)) if !self.output_matches(&expected, &output.stdout) => {
+                Err(RunFailure::WrongOutput(output))
+            }
+            Ok(_) => Ok(output),
+            Err(e) => {
+                output.stdout = format!("The expected output of {} is missing. {e}", self.name);
+                Err(RunFailure::Failed(output))
+            }
         }
     }
 
-    pub fn state(&self) -> State {
-        let mut source_file =
-            File::open(&self.path).expect("We were unable to open the exercise file!");
-
-        let source = {
-            let mut s = String::new();
-            source_file
-                .read_to_string(&mut s)
-                .expect("We were unable to read the exercise file!");
-            s
-        };
+    // The codes of the errors in the compiler output, like "E0382"
+    pub fn error_codes(output: &ExerciseOutput) -> Vec<String> {
+        let stderr = util::strip_colors(&output.stderr);
+        let re = Regex::new(r"error\[(E\d{4})\]").unwrap();
+        let mut codes: Vec<String> = re
+            .captures_iter(&stderr)
+            .map(|caps| caps[1].to_string())
+            .collect();
+        codes.dedup();
+        codes
+    }
+
+    // What to feed the exercise on stdin, if anything
+    pub fn input(&self) -> io::Result<Option<String>> {
+        if let Some(input) = &self.stdin {
+            return Ok(Some(input.clone()));
+        }
+        match &self.stdin_file {
+            Some(path) => fs::read_to_string(path).map(Some).map_err(|e| {
+                io::Error::new(e.kind(), format!("Failed to read {}: {e}", path.display()))
+            }),
+            None => Ok(None),
+        }
+    }
+
+    // What the exercise has to print to pass, if anything
+    pub fn expected_output(&self) -> io::Result<Option<String>> {
+        if let Some(expected) = &self.expected_output {
+            return Ok(Some(expected.clone()));
+        }
+        match &self.expected_output_file {
+            Some(path) => fs::read_to_string(path).map(Some).map_err(|e| {
+                io::Error::new(e.kind(), format!("Failed to read {}: {e}", path.display()))
+            }),
+            None => Ok(None),
+        }
+    }
+
+    // Bring an output into the shape it's compared in
+    pub fn normalize_output(&self, output: &str) -> String {
+        if !self.normalize_whitespace {
+            return output.to_string();
+        }
+        let lines: Vec<&str> = output.lines().map(str::trim_end).collect();
+        let end = lines
+            .iter()
+            .rposition(|line| !line.is_empty())
+            .map_or(0, |i| i + 1);
+        lines[..end]
+            .iter()
+            .map(|line| format!("{line}\n"))
+            .collect()
+    }
+
+    fn output_matches(&self, expected: &str, actual: &str) -> bool {
+        self.normalize_output(expected) == self.normalize_output(actual)
+    }
 
+    // The limits the binary or test harness runs within.
+    // The exercise's own limits take precedence over the configured ones
+    pub fn limits(&self) -> Limits {
+        let config = config::get();
+        Limits {
+            timeout: Duration::from_secs(self.timeout.unwrap_or_else(|| config.timeout())),
+            memory: self.memory_limit.or_else(|| config.memory_limit()),
+            cpu_time: self
+                .cpu_time_limit
+                .unwrap_or_else(|| config.cpu_time_limit()),
+            processes: self.process_limit,
+        }
+    }
+
+    pub fn state(&self) -> State {
         let re = Regex::new(I_AM_DONE_REGEX).unwrap();
 
-        if !re.is_match(&source) {
+        // In a Cargo exercise, the marker may be in any of its Rust files
+        let source = self
+            .rust_sources()
+            .iter()
+            .map(|path| {
+                let mut source_file =
+                    File::open(path).expect("We were unable to open the exercise file!");
+                let mut s = String::new();
+                source_file
+                    .read_to_string(&mut s)
+                    .expect("We were unable to read the exercise file!");
+                s
+            })
+            .find(|source| re.is_match(source));
+
+        let Some(source) = source else {
             return State::Done;
-        }
+        };
 
         let matched_line_index = source
             .lines()
@@ -241,14 +705,117 @@ path = "{}.rs""#,
         State::Pending(context)
     }
 
-    // Check that the exercise looks to be solved using self.state()
-    // This is not the best way to check since
-    // the user can just remove the "I AM NOT DONE" string from the file
-    // without actually having solved anything.
-    // The only other way to truly check this would to compile and run
-    // the exercise; which would be both costly and counterintuitive
-    pub fn looks_done(&self) -> bool {
-        self.state() == State::Done
+    // The directory under `exercises/` the exercise lives in, e.g. `intro`.
+    // Exercises directly inside `exercises/`, like the quizzes, have none
+    pub fn category(&self) -> Option<&str> {
+        let dir = self.path.strip_prefix("exercises").ok()?.parent()?;
+        dir.to_str().filter(|dir| !dir.is_empty())
+    }
+
+    // The path to the exercise's reference solution, if there is one.
+    // Unless info.toml says otherwise, the solution to `exercises/a/b.rs`
+    // lives in `solutions/a/b.rs`
+    pub fn solution_path(&self) -> Option<PathBuf> {
+        let path = match &self.solution {
+            Some(path) => path.clone(),
+            None => {
+                let relative = self.path.strip_prefix("exercises").unwrap_or(&self.path);
+                Path::new("solutions").join(relative)
+            }
+        };
+        path.exists().then_some(path)
+    }
+
+    // The files the exercise is made of: its path, or every file in the
+    // package directory of a Cargo exercise, apart from what Cargo generates
+    pub fn sources(&self) -> Vec<PathBuf> {
+        if !self.path.is_dir() {
+            return vec![self.path.clone()];
+        }
+        let mut sources = Vec::new();
+        collect_sources(&self.path, &mut sources);
+        sources.sort();
+        sources
+    }
+
+    // The Rust files among the exercise's sources
+    pub fn rust_sources(&self) -> Vec<PathBuf> {
+        let mut sources = self.sources();
+        if self.path.is_dir() {
+            sources.retain(|path| path.extension() == Some("rs".as_ref()));
+        }
+        sources
+    }
+
+    // The SHA-256 hash of the exercise's source, as a hex string.
+    // The files of a Cargo exercise are hashed along with their paths.
+    // The hash is only computed again once one of the files changed
+    pub fn source_hash(&self) -> String {
+        let sources = self.sources();
+        let stamps: Vec<FileStamp> = sources.iter().map(|path| FileStamp::of(path)).collect();
+        self.hash_sources(&sources, stamps)
+    }
+
+    // The stamps of the exercise's files, which tell whether any of them
+    // changed since without reading them
+    pub fn source_stamps(&self) -> Vec<FileStamp> {
+        self.sources()
+            .iter()
+            .map(|path| FileStamp::of(path))
+            .collect()
+    }
+
+    fn hash_sources(&self, sources: &[PathBuf], stamps: Vec<FileStamp>) -> String {
+        let mut hashes = SOURCE_HASHES.lock().unwrap();
+        if let Some((cached, hash)) = hashes.get(&self.path) {
+            if *cached == stamps {
+                return hash.clone();
+            }
+        }
+
+        let mut hasher = Sha256::new();
+        for path in sources {
+            if let Ok(relative) = path.strip_prefix(&self.path) {
+                hasher.update(relative.to_string_lossy().as_bytes());
+            }
+            hasher.update(fs::read(path).expect("We were unable to read the exercise file!"));
+        }
+        let hash: String = hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        hashes.insert(self.path.clone(), (stamps, hash.clone()));
+        hash
+    }
+}
+
+// The hashes `source_hash` computed, keyed by exercise path,
+// with the stamps of the files they were computed from
+static SOURCE_HASHES: Mutex<BTreeMap<PathBuf, (Vec<FileStamp>, String)>> =
+    Mutex::new(BTreeMap::new());
+
+/// What tells whether a file changed without reading it
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct FileStamp {
+    path: PathBuf,
+    // Nanoseconds since the Unix epoch at which the file was last modified
+    modified: Option<u64>,
+    len: Option<u64>,
+}
+
+impl FileStamp {
+    fn of(path: &Path) -> FileStamp {
+        let metadata = fs::metadata(path).ok();
+        FileStamp {
+            path: path.to_path_buf(),
+            modified: metadata
+                .as_ref()
+                .and_then(|m| m.modified().ok())
+                .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                .and_then(|since| since.as_nanos().try_into().ok()),
+            len: metadata.map(|m| m.len()),
+        }
     }
 }
 
@@ -258,6 +825,88 @@ impl Display for Exercise {
     }
 }
 
+/// The rustc given with `--rustc` or `RUSTLINGS_RUSTC`, or else
+/// the default one, for everything that isn't about a single exercise
+pub fn rustc() -> Command {
+    Command::new(env::var_os(RUSTC_ENV).unwrap_or_else(|| "rustc".into()))
+}
+
+/// The directories exercise packs keep their single-file exercises in,
+/// when that isn't `exercises/`. The packages of Cargo exercises aren't included
+pub fn pack_dirs(exercises: &[Exercise]) -> Vec<&Path> {
+    let mut dirs: Vec<&Path> = exercises
+        .iter()
+        .filter(|e| !e.path.starts_with("exercises") && !matches!(e.mode, Mode::Cargo))
+        .filter_map(|e| e.path.parent())
+        .map(|dir| {
+            if dir.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                dir
+            }
+        })
+        .collect();
+    dirs.sort();
+    dirs.dedup();
+    dirs
+}
+
+// The contents of the files of an exercise, by their path relative to the
+// exercise's path. A single-file exercise has one file with an empty path
+pub type SourceFiles = Vec<(PathBuf, Vec<u8>)>;
+
+// Read the file or the package directory at the path, like `sources()`
+pub fn read_files(path: &Path) -> io::Result<SourceFiles> {
+    if !path.is_dir() {
+        return Ok(vec![(PathBuf::new(), fs::read(path)?)]);
+    }
+    let mut sources = Vec::new();
+    collect_sources(path, &mut sources);
+    sources.sort();
+    sources
+        .into_iter()
+        .map(|source| {
+            let contents = fs::read(&source)?;
+            let relative = source.strip_prefix(path).unwrap_or(&source).to_path_buf();
+            Ok((relative, contents))
+        })
+        .collect()
+}
+
+// Write the files to the path, replacing whatever was there
+pub fn write_files(path: &Path, files: &SourceFiles) -> io::Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)?;
+    }
+    for (relative, contents) in files {
+        let file = if relative.as_os_str().is_empty() {
+            path.to_path_buf()
+        } else {
+            path.join(relative)
+        };
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(file, contents)?;
+    }
+    Ok(())
+}
+
+// The files of a Cargo package, without what Cargo generates
+pub fn collect_sources(dir: &Path, sources: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        if path.is_dir() {
+            if name != "target" {
+                collect_sources(&path, sources);
+            }
+        } else if name != "Cargo.lock" {
+            sources.push(path);
+        }
+    }
+}
+
 #[inline]
 fn clean() {
     let _ignored = remove_file(temp_file());
@@ -266,7 +915,6 @@ fn clean() {
 #[cfg(test)]
 mod test {
     use super::*;
-    use std::path::Path;
 
     #[test]
     fn test_clean() {
@@ -275,11 +923,11 @@ mod test {
             name: String::from("example"),
             path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
             mode: Mode::Compile,
-            hint: String::from(""),
+            ..Default::default()
         };
         let compiled = exercise.compile().unwrap();
         drop(compiled);
-        assert!(!Path::new(&temp_file()).exists());
+        assert!(!temp_file().exists());
     }
 
     #[test]
@@ -288,7 +936,7 @@ mod test {
             name: "pending_exercise".into(),
             path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
             mode: Mode::Compile,
-            hint: String::new(),
+            ..Default::default()
         };
 
         let state = exercise.state();
@@ -329,19 +977,42 @@ mod test {
             name: "finished_exercise".into(),
             path: PathBuf::from("tests/fixture/state/finished_exercise.rs"),
             mode: Mode::Compile,
-            hint: String::new(),
+            ..Default::default()
         };
 
         assert_eq!(exercise.state(), State::Done);
     }
 
+    #[test]
+    fn test_single_hint_is_one_level() {
+        let list: ExerciseList = toml::from_str(
+            r#"
+            [[exercises]]
+            name = "single"
+            path = "single.rs"
+            mode = "compile"
+            hint = "Only hint"
+
+            [[exercises]]
+            name = "levels"
+            path = "levels.rs"
+            mode = "compile"
+            hints = ["First hint", "Second hint"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(list.exercises[0].hints, vec!["Only hint"]);
+        assert_eq!(list.exercises[1].hints, vec!["First hint", "Second hint"]);
+    }
+
     #[test]
     fn test_exercise_with_output() {
         let exercise = Exercise {
             name: "exercise_with_output".into(),
             path: PathBuf::from("tests/fixture/success/testSuccess.rs"),
             mode: Mode::Test,
-            hint: String::new(),
+            ..Default::default()
         };
         let out = exercise.compile().unwrap().run().unwrap();
         assert!(out.stdout.contains("THIS TEST TOO SHALL PASS"));
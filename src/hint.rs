@@ -0,0 +1,57 @@
+use crate::exercise::Exercise;
+use crate::state::StateFile;
+use console::style;
+
+// Which of an exercise's hints to show
+pub enum HintLevel {
+    // Every hint revealed so far, revealing the first one if needed
+    Revealed,
+    // The next hint that hasn't been revealed yet
+    Next,
+    // A specific hint, counting from 1
+    Level(usize),
+}
+
+// Show the requested hints of the given exercise,
+// and remember how far they've been revealed
+pub fn hint(exercise: &Exercise, state: &mut StateFile, level: HintLevel) -> Result<(), ()> {
+    let total = exercise.hints.len();
+    if total == 0 {
+        println!("There are no hints for {}", exercise.name);
+        return Ok(());
+    }
+    let revealed = state.hint_level(exercise);
+    let levels = match level {
+        HintLevel::Revealed => 1..=revealed.clamp(1, total),
+        // Once every hint is revealed, show all of them again
+        HintLevel::Next if revealed >= total => 1..=total,
+        HintLevel::Next => revealed + 1..=revealed + 1,
+        HintLevel::Level(level) if (1..=total).contains(&level) => level..=level,
+        HintLevel::Level(level) => {
+            let message = format!("{} only has {total} hints, not {level}", exercise.name);
+            warn!("{}", message);
+            return Err(());
+        }
+    };
+    if let Err(e) = state.reveal_hints(exercise, *levels.end()) {
+        warn!("Failed to save your progress: {}", e);
+    }
+
+    // A single hint is printed as is, just like before hints had levels
+    if total == 1 {
+        println!("{}", exercise.hints[0]);
+        return Ok(());
+    }
+    for level in levels.clone() {
+        println!("{}", style(format!("Hint {level}/{total}:")).bold());
+        println!("{}", exercise.hints[level - 1].trim_start_matches('\n'));
+        println!();
+    }
+    if *levels.end() < total {
+        println!(
+            "If that's not enough, type `hint` again in watch mode, or run `rustlings hint {} --next`.",
+            exercise.name
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use crate::exercise::{self, Exercise, Mode};
+use crate::state::now;
+use crate::verify::Outcome;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+const HISTORY_DIR: &str = ".rustlings/history";
+
+// A version of an exercise that was saved while working on it
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    // Counting from 1, in the order the snapshots were taken
+    id: usize,
+    // Seconds since the Unix epoch at which the snapshot was taken
+    taken_at: u64,
+    // The SHA-256 hash of the snapshot's source
+    source_hash: String,
+    // The name of the outcome of checking the snapshot, see `Outcome::name`,
+    // or `unchecked` if it was only taken to avoid losing it on `restore`
+    outcome: String,
+}
+
+// The snapshots of a single exercise, kept in `.rustlings/history/<name>/`
+// as one file per snapshot, plus an index of all of them.
+// Snapshots of Cargo exercises are directories instead
+struct History {
+    dir: PathBuf,
+    extension: &'static str,
+    snapshots: Vec<Snapshot>,
+}
+
+impl History {
+    fn load(exercise: &Exercise) -> History {
+        let dir = PathBuf::from(HISTORY_DIR).join(&exercise.name);
+        let snapshots = fs::read_to_string(dir.join("index.json"))
+            .ok()
+            .and_then(|index| serde_json::from_str(&index).ok())
+            .unwrap_or_default();
+        let extension = match exercise.mode {
+            Mode::Cargo => "",
+            _ => ".rs",
+        };
+        History {
+            dir,
+            extension,
+            snapshots,
+        }
+    }
+
+    fn snapshot_path(&self, id: usize) -> PathBuf {
+        self.dir.join(format!("{id}{}", self.extension))
+    }
+
+    // Save the current source of the exercise, unless an identical
+    // snapshot exists already. Returns the id of the snapshot taken
+    fn take(&mut self, exercise: &Exercise, outcome: &str) -> io::Result<Option<usize>> {
+        let source_hash = exercise.source_hash();
+        if self.snapshots.iter().any(|s| s.source_hash == source_hash) {
+            return Ok(None);
+        }
+        let id = self.snapshots.last().map_or(1, |s| s.id + 1);
+        let files = exercise::read_files(&exercise.path)?;
+        exercise::write_files(&self.snapshot_path(id), &files)?;
+        self.snapshots.push(Snapshot {
+            id,
+            taken_at: now(),
+            source_hash,
+            outcome: outcome.to_string(),
+        });
+        let index =
+            serde_json::to_string_pretty(&self.snapshots).expect("Failed to serialize to JSON");
+        fs::write(self.dir.join("index.json"), index)?;
+        Ok(Some(id))
+    }
+}
+
+/// Snapshot the exercise after watch mode checked it,
+/// unless this version of it was snapshotted before.
+pub fn record(exercise: &Exercise, outcome: &Outcome) {
+    if let Err(e) = History::load(exercise).take(exercise, outcome.name()) {
+        warn!("Failed to save a snapshot of your exercise: {}", e);
+    }
+}
+
+// List the snapshots of the exercise with their outcomes, oldest first
+pub fn history(exercise: &Exercise) -> Result<(), ()> {
+    let history = History::load(exercise);
+    if history.snapshots.is_empty() {
+        println!(
+            "There are no snapshots of {exercise} yet, they're taken whenever `rustlings watch` checks it"
+        );
+        return Ok(());
+    }
+    let now = now();
+    println!("{:<8}\t{:<15}\tOutcome", "Snapshot", "Taken");
+    for snapshot in &history.snapshots {
+        println!(
+            "{:<8}\t{:<15}\t{}",
+            snapshot.id,
+            format_age(now.saturating_sub(snapshot.taken_at)),
+            snapshot.outcome
+        );
+    }
+    println!(
+        "\nBring one back with `rustlings restore {} <snapshot>`",
+        exercise.name
+    );
+    Ok(())
+}
+
+// Replace the exercise with one of its snapshots, after taking
+// a snapshot of the current version so it can't get lost
+pub fn restore(exercise: &Exercise, id: usize) -> Result<(), ()> {
+    let mut history = History::load(exercise);
+    if !history.snapshots.iter().any(|s| s.id == id) {
+        warn!(
+            "There is no snapshot {}, see `rustlings history` for the available ones",
+            format!("{id} of {exercise}")
+        );
+        return Err(());
+    }
+    let restored = history.take(exercise, "unchecked").and_then(|saved| {
+        let files = exercise::read_files(&history.snapshot_path(id))?;
+        exercise::write_files(&exercise.path, &files)?;
+        Ok(saved)
+    });
+    match restored {
+        Ok(saved) => {
+            success!("Restored {}", format!("{exercise} to snapshot {id}"));
+            if let Some(saved) = saved {
+                println!("Your previous version was saved as snapshot {saved}");
+            }
+            Ok(())
+        }
+        Err(e) => {
+            warn!("Failed to restore {}", format!("{exercise}: {e}"));
+            Err(())
+        }
+    }
+}
+
+fn format_age(seconds: u64) -> String {
+    match seconds {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{} min ago", seconds / 60),
+        3600..=86399 => format!("{} h ago", seconds / 3600),
+        _ => format!("{} days ago", seconds / 86400),
+    }
+}
@@ -0,0 +1,209 @@
+use crate::session;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Read, Write};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::Duration;
+use wait_timeout::ChildExt;
+
+/// How long an exercise may run unless its `timeout` says otherwise
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+/// How many seconds of CPU time an exercise may use unless its `cpu_time_limit` says otherwise
+pub const DEFAULT_CPU_TIME_LIMIT: u64 = 30;
+
+/// How much of the machine learner code may use.
+/// Only the timeout is enforced outside of Linux
+#[derive(Serialize)]
+pub struct Limits {
+    /// How long the process may run before it's killed
+    pub timeout: Duration,
+    /// How many MiB of address space the process may map, if limited.
+    /// Test harnesses reserve address space for every thread they start,
+    /// so this is only safe to set for exercises known to need little
+    pub memory: Option<u64>,
+    /// How many seconds of CPU time the process may use
+    pub cpu_time: u64,
+    /// How many processes and threads the user may have at once, if limited.
+    /// Like `ulimit -u`, this counts everything running as the same user
+    pub processes: Option<u64>,
+}
+
+/// A resource a process can use up
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Resource {
+    Memory,
+    CpuTime,
+}
+
+impl Display for Resource {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Resource::Memory => write!(f, "memory"),
+            Resource::CpuTime => write!(f, "CPU time"),
+        }
+    }
+}
+
+/// How a process that ran within limits ended
+pub enum Exit {
+    /// The process exited by itself
+    Status(ExitStatus),
+    /// The process ran out of time and was killed, along with its children
+    TimedOut,
+    /// The process was stopped for using up its share of a resource
+    LimitExceeded(Resource),
+}
+
+/// The captured output of a process that ran within limits
+pub struct LimitedOutput {
+    pub exit: Exit,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+/// Run the command to completion within the limits, feeding it the input
+/// on stdin, if any, and capturing its output
+pub fn output(
+    command: &mut Command,
+    input: Option<&[u8]>,
+    limits: &Limits,
+) -> io::Result<LimitedOutput> {
+    let stdin = if input.is_some() {
+        Stdio::piped()
+    } else {
+        Stdio::null()
+    };
+    command
+        .stdin(stdin)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(target_os = "linux")]
+    set_rlimits(command, limits);
+    let mut child = session::spawn_group(command)?;
+    let result = wait(&mut child, input, limits);
+    session::release_group(&child);
+    result
+}
+
+// Wait for the process to exit or to run out of time
+fn wait(child: &mut Child, input: Option<&[u8]>, limits: &Limits) -> io::Result<LimitedOutput> {
+    // Feed and drain the pipes while waiting,
+    // so that the process can't block on any of them
+    if let (Some(mut pipe), Some(input)) = (child.stdin.take(), input) {
+        let input = input.to_vec();
+        // A process that doesn't read all of its input closes the pipe early
+        thread::spawn(move || pipe.write_all(&input));
+    }
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+    let status = match child.wait_timeout(limits.timeout)? {
+        Some(status) => Some(status),
+        None => {
+            kill_tree(child);
+            child.wait()?;
+            None
+        }
+    };
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    let exit = match status {
+        None => Exit::TimedOut,
+        Some(status) => match exceeded(status, &stderr) {
+            Some(resource) => Exit::LimitExceeded(resource),
+            None => Exit::Status(status),
+        },
+    };
+    Ok(LimitedOutput {
+        exit,
+        stdout,
+        stderr,
+    })
+}
+
+// Apply the limits to the process once it's forked, before it runs.
+// Only soft limits are lowered, so a hard limit that's lower
+// than ours still applies
+#[cfg(target_os = "linux")]
+fn set_rlimits(command: &mut Command, limits: &Limits) {
+    use std::os::unix::process::CommandExt;
+
+    let rlimits = [
+        (
+            libc::RLIMIT_AS,
+            limits
+                .memory
+                .map(|memory| memory.saturating_mul(1024 * 1024)),
+        ),
+        (libc::RLIMIT_CPU, Some(limits.cpu_time)),
+        (libc::RLIMIT_NPROC, limits.processes),
+    ];
+    // SAFETY: getrlimit and setrlimit are async-signal-safe, and nothing
+    // is allocated between fork and exec
+    unsafe {
+        command.pre_exec(move || {
+            for (resource, limit) in rlimits {
+                let Some(limit) = limit else { continue };
+                let mut rlimit = libc::rlimit {
+                    rlim_cur: 0,
+                    rlim_max: 0,
+                };
+                if libc::getrlimit(resource, &mut rlimit) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                rlimit.rlim_cur = (limit as libc::rlim_t).min(rlimit.rlim_max);
+                if libc::setrlimit(resource, &rlimit) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+            Ok(())
+        });
+    }
+}
+
+// Tell whether the process died because it ran into one of its rlimits:
+// a failed allocation aborts Rust programs, and running out of CPU time
+// raises SIGXCPU
+#[cfg(target_os = "linux")]
+fn exceeded(status: ExitStatus, stderr: &[u8]) -> Option<Resource> {
+    use std::os::unix::process::ExitStatusExt;
+
+    match status.signal()? {
+        libc::SIGXCPU => Some(Resource::CpuTime),
+        libc::SIGABRT if String::from_utf8_lossy(stderr).contains("memory allocation of") => {
+            Some(Resource::Memory)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn exceeded(_status: ExitStatus, _stderr: &[u8]) -> Option<Resource> {
+    None
+}
+
+fn read_in_background(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
+
+#[cfg(unix)]
+fn kill_tree(child: &mut Child) {
+    // A negative pid signals the whole process group
+    let group = -(child.id() as libc::pid_t);
+    // SAFETY: kill has no memory safety requirements
+    if unsafe { libc::kill(group, libc::SIGKILL) } != 0 {
+        let _ = child.kill();
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_tree(child: &mut Child) {
+    let _ = child.kill();
+}
@@ -1,15 +1,27 @@
-use crate::exercise::{Exercise, ExerciseList};
+use crate::check::check_manifest;
+use crate::clean::clean;
+use crate::config::Color;
+use crate::editor::edit;
+use crate::exercise::{Difficulty, Exercise, Mode};
+use crate::hint::{hint, HintLevel};
+use crate::history::{history, restore};
 use crate::project::RustAnalyzerProject;
+use crate::report::{Format, ListEntry};
 use crate::run::{reset, run};
-use crate::verify::verify;
+use crate::scaffold::new_exercise;
+use crate::solution::solution;
+use crate::state::StateFile;
+use crate::verify::{verify, VerifyOptions};
 use clap::{Parser, Subcommand};
 use console::Emoji;
 use notify::DebouncedEvent;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::env;
 use std::ffi::OsStr;
 use std::fs;
 use std::io::{self, prelude::*};
-use std::path::Path;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{channel, RecvTimeoutError};
@@ -20,9 +32,27 @@ use std::time::Duration;
 #[macro_use]
 mod ui;
 
+mod cache;
+mod check;
+mod clean;
+mod config;
+mod diff;
+mod editor;
 mod exercise;
+mod hint;
+mod history;
+mod limits;
+mod manifest;
+mod pristine;
 mod project;
+mod report;
 mod run;
+mod rustfmt;
+mod scaffold;
+mod session;
+mod solution;
+mod state;
+mod util;
 mod verify;
 
 /// Rustlings is a collection of small exercises to get you used to writing and reading Rust code
@@ -32,6 +62,14 @@ struct Args {
     /// Show outputs from the test exercises
     #[arg(long)]
     nocapture: bool,
+    /// Load the exercises of an additional pack file, after the ones in
+    /// info.toml and info.d/
+    #[arg(long, value_name = "FILE", global = true)]
+    pack: Vec<PathBuf>,
+    /// The rustc to compile exercises with instead of the one rustup picks.
+    /// Defaults to the RUSTLINGS_RUSTC environment variable
+    #[arg(long, value_name = "PATH", global = true)]
+    rustc: Option<PathBuf>,
     #[command(subcommand)]
     command: Option<Subcommands>,
 }
@@ -39,7 +77,17 @@ struct Args {
 #[derive(Subcommand)]
 enum Subcommands {
     /// Verify all exercises according to the recommended order
-    Verify,
+    Verify {
+        /// Number of exercises to compile and run concurrently
+        #[arg(short, long, default_value = "1")]
+        jobs: NonZeroUsize,
+        /// The output format
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+        /// Check every exercise and write a JUnit XML report to the given file
+        #[arg(long, value_name = "FILE")]
+        junit: Option<PathBuf>,
+    },
     /// Rerun `verify` when files were edited
     Watch {
         /// Show hints on success
@@ -50,16 +98,64 @@ enum Subcommands {
     Run {
         /// The name of the exercise
         name: String,
+        /// Feed the exercise this file on stdin instead of its own input,
+        /// or `-` to pass on what you type
+        #[arg(long, value_name = "FILE")]
+        stdin: Option<PathBuf>,
+    },
+    /// Format an exercise with rustfmt
+    Fmt {
+        /// The name of the exercise
+        name: String,
     },
-    /// Reset a single exercise using "git stash -- <filename>"
+    /// Reset exercises to their original version, backing up your changes
     Reset {
         /// The name of the exercise
+        #[arg(required_unless_present_any = ["category", "all"])]
+        name: Option<String>,
+        /// Reset every exercise in this directory under `exercises/`
+        #[arg(short, long, conflicts_with_all = ["name", "all"])]
+        category: Option<String>,
+        /// Reset every exercise
+        #[arg(short, long, conflicts_with = "name")]
+        all: bool,
+    },
+    /// Open an exercise in your editor, at the first compiler error or its
+    /// `I AM NOT DONE` comment
+    Edit {
+        /// The name of the exercise, or `next` for the next unsolved one
         name: String,
     },
-    /// Return a hint for the given exercise
+    /// List the snapshots watch mode took of an exercise
+    History {
+        /// The name of the exercise
+        name: String,
+    },
+    /// Bring back a snapshot of an exercise
+    Restore {
+        /// The name of the exercise
+        name: String,
+        /// The number of the snapshot, as shown by `history`
+        snapshot: usize,
+    },
+    /// Show the reference solution of an exercise you've solved
+    Solution {
+        /// The name of the exercise
+        name: String,
+        /// Show a diff between your solution and the reference solution
+        #[arg(short, long)]
+        diff: bool,
+    },
+    /// Return the hints revealed so far for the given exercise
     Hint {
         /// The name of the exercise
         name: String,
+        /// Only show the hint with this number, counting from 1
+        #[arg(short, long, conflicts_with = "next")]
+        level: Option<usize>,
+        /// Reveal the next hint
+        #[arg(short, long)]
+        next: bool,
     },
     /// List the exercises available in Rustlings
     List {
@@ -79,13 +175,56 @@ enum Subcommands {
         /// Display only exercises that have been solved
         #[arg(short, long)]
         solved: bool,
+        /// Display only exercises with one of these tags.
+        /// Comma separated tags are accepted
+        #[arg(short, long)]
+        tag: Option<String>,
+        /// Display only exercises of this difficulty
+        #[arg(short, long, value_enum)]
+        difficulty: Option<Difficulty>,
+        /// The output format
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
     },
     /// Enable rust-analyzer for exercises
     Lsp,
+    /// Create a new exercise from a template and register it
+    New {
+        /// The name of the exercise
+        name: String,
+        /// The directory under `exercises/` to create the exercise in
+        #[arg(short, long)]
+        category: String,
+        /// How the exercise is checked
+        #[arg(short, long, value_enum, default_value_t)]
+        mode: Mode,
+        /// The manifest to register the exercise in
+        #[arg(long, value_name = "FILE", default_value = manifest::MAIN_MANIFEST)]
+        manifest: PathBuf,
+    },
+    /// Check info.toml, the exercise packs and the exercises for problems
+    CheckManifest,
+    /// Show the effective settings and where they come from
+    Config,
+    /// Remove build artifacts left behind in the exercises and temp directories
+    Clean {
+        /// Only list what would be removed
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 fn main() {
     let args = Args::parse();
+    // Exercises find the rustc to use in the environment.
+    // It's set before any threads are started
+    if let Some(rustc) = &args.rustc {
+        env::set_var(exercise::RUSTC_ENV, rustc);
+    }
+    // Build artifacts go to a directory of this session,
+    // which is removed however rustlings exits
+    let _session = session::Guard;
+    session::handle_interrupts();
 
     if args.command.is_none() {
         println!("\n{WELCOME}\n");
@@ -97,23 +236,57 @@ fn main() {
             std::env::current_exe().unwrap().to_str().unwrap()
         );
         println!("Try `cd rustlings/`!");
-        std::process::exit(1);
+        session::exit(1);
+    }
+
+    // The other commands can't even start with a broken manifest
+    if let Some(Subcommands::CheckManifest) = args.command {
+        match check_manifest(&args.pack) {
+            Ok(()) => session::exit(0),
+            Err(()) => session::exit(1),
+        }
+    }
+
+    // Flags override the configuration files and the environment
+    let flags = config::Settings {
+        verbose: args.nocapture.then_some(true),
+        success_hints: matches!(
+            args.command,
+            Some(Subcommands::Watch {
+                success_hints: true
+            })
+        )
+        .then_some(true),
+        ..Default::default()
+    };
+    let config = config::init(flags).unwrap_or_else(|e| {
+        println!("{e}");
+        session::exit(1);
+    });
+    match config.color() {
+        Color::Always => console::set_colors_enabled(true),
+        Color::Never => console::set_colors_enabled(false),
+        Color::Auto => {}
     }
 
     if !rustc_exists() {
         println!("We cannot find `rustc`.");
         println!("Try running `rustc --version` to diagnose your problem.");
         println!("For instructions on how to install Rust, check the README.");
-        std::process::exit(1);
+        session::exit(1);
     }
 
-    let toml_str = &fs::read_to_string("info.toml").unwrap();
-    let exercises = toml::from_str::<ExerciseList>(toml_str).unwrap().exercises;
-    let verbose = args.nocapture;
+    let exercises = manifest::load(&args.pack).unwrap_or_else(|e| {
+        println!("{e}");
+        session::exit(1);
+    });
+    let verbose = config.verbose();
+    pristine::snapshot(&exercises);
+    let mut state = StateFile::load(&exercises);
 
     let command = args.command.unwrap_or_else(|| {
         println!("{DEFAULT_OUT}\n");
-        std::process::exit(0);
+        session::exit(0);
     });
 
     match command {
@@ -123,9 +296,17 @@ fn main() {
             filter,
             unsolved,
             solved,
+            tag,
+            difficulty,
+            format,
         } => {
-            if !paths && !names {
-                println!("{:<17}\t{:<46}\t{:<7}", "Name", "Path", "Status");
+            let human = format == Format::Human;
+            let mut rows = Vec::new();
+            if human && !paths && !names {
+                println!(
+                    "{:<17}\t{:<46}\t{:<7}\t{:<10}\t{:<9}\tTags",
+                    "Name", "Path", "Status", "Difficulty", "Chapter"
+                );
             }
             let mut exercises_done: u16 = 0;
             let filters = filter.clone().unwrap_or_default().to_lowercase();
@@ -135,24 +316,36 @@ fn main() {
                     .split(',')
                     .filter(|f| !f.trim().is_empty())
                     .any(|f| e.name.contains(f) || fname.contains(f));
-                let status = if e.looks_done() {
+                let done = state.is_done(e);
+                let status = if done {
                     exercises_done += 1;
                     "Done"
                 } else {
                     "Pending"
                 };
-                let solve_cond = {
-                    (e.looks_done() && solved)
-                        || (!e.looks_done() && unsolved)
-                        || (!solved && !unsolved)
-                };
-                if solve_cond && (filter_cond || filter.is_none()) {
+                let solve_cond = (done && solved) || (!done && unsolved) || (!solved && !unsolved);
+                let tag_cond = tag.as_ref().is_none_or(|tags| {
+                    tags.split(',')
+                        .any(|t| e.tags.iter().any(|tag| tag == t.trim()))
+                });
+                let difficulty_cond = difficulty.is_none() || e.difficulty == difficulty;
+                if solve_cond && (filter_cond || filter.is_none()) && tag_cond && difficulty_cond {
+                    if !human {
+                        rows.push(ListEntry::new(e, done));
+                        return;
+                    }
                     let line = if paths {
                         format!("{fname}\n")
                     } else if names {
                         format!("{}\n", e.name)
                     } else {
-                        format!("{:<17}\t{fname:<46}\t{status:<7}\n", e.name)
+                        format!(
+                            "{:<17}\t{fname:<46}\t{status:<7}\t{:<10}\t{:<9}\t{}\n",
+                            e.name,
+                            e.difficulty.map(|d| d.to_string()).unwrap_or_default(),
+                            e.book_chapter.as_deref().unwrap_or_default(),
+                            e.tags.join(", ")
+                        )
                     };
                     // Somehow using println! leads to the binary panicking
                     // when its output is piped.
@@ -162,13 +355,17 @@ fn main() {
                         let mut handle = stdout.lock();
                         handle.write_all(line.as_bytes()).unwrap_or_else(|e| {
                             match e.kind() {
-                                std::io::ErrorKind::BrokenPipe => std::process::exit(0),
-                                _ => std::process::exit(1),
+                                std::io::ErrorKind::BrokenPipe => session::exit(0),
+                                _ => session::exit(1),
                             };
                         });
                     }
                 }
             });
+            if !human {
+                report::write_rows(format, &rows);
+                session::exit(0);
+            }
             let percentage_progress = exercises_done as f32 / exercises.len() as f32 * 100.0;
             println!(
                 "Progress: You completed {} / {} exercises ({:.1} %).",
@@ -176,30 +373,115 @@ fn main() {
                 exercises.len(),
                 percentage_progress
             );
-            std::process::exit(0);
+            session::exit(0);
         }
 
-        Subcommands::Run { name } => {
-            let exercise = find_exercise(&name, &exercises);
+        Subcommands::Run { name, stdin } => {
+            let mut exercise = find_exercise(&name, &exercises, &state).clone();
+            if let Some(path) = stdin {
+                let input = if path == Path::new("-") {
+                    io::read_to_string(io::stdin())
+                } else {
+                    fs::read_to_string(&path)
+                };
+                let input = input.unwrap_or_else(|e| {
+                    println!("Failed to read the input for {}: {e}", exercise.name);
+                    session::exit(1);
+                });
+                // The expected output only holds for the exercise's own input
+                exercise.stdin = Some(input);
+                exercise.stdin_file = None;
+                exercise.expected_output = None;
+                exercise.expected_output_file = None;
+            }
+
+            run(&exercise, verbose).unwrap_or_else(|_| session::exit(1));
+        }
+
+        Subcommands::Fmt { name } => {
+            let exercise = find_exercise(&name, &exercises, &state);
 
-            run(exercise, verbose).unwrap_or_else(|_| std::process::exit(1));
+            rustfmt::format(exercise).unwrap_or_else(|_| session::exit(1));
         }
 
-        Subcommands::Reset { name } => {
-            let exercise = find_exercise(&name, &exercises);
+        Subcommands::Reset {
+            name,
+            category,
+            all,
+        } => {
+            let selected: Vec<&Exercise> = match (name, category) {
+                (Some(name), _) => vec![find_exercise(&name, &exercises, &state)],
+                (None, Some(category)) => exercises
+                    .iter()
+                    .filter(|e| e.category() == Some(category.as_str()))
+                    .collect(),
+                (None, None) if all => exercises.iter().collect(),
+                (None, None) => unreachable!("clap requires a name, a category or --all"),
+            };
+            if selected.is_empty() {
+                println!("No exercises found to reset");
+                session::exit(1);
+            }
+            // Keep resetting the others when one fails, but still report it
+            let failed = selected
+                .into_iter()
+                .filter(|exercise| reset(exercise).is_err())
+                .count();
+            if failed > 0 {
+                session::exit(1);
+            }
+        }
+
+        Subcommands::Edit { name } => {
+            let exercise = find_exercise(&name, &exercises, &state);
 
-            reset(exercise).unwrap_or_else(|_| std::process::exit(1));
+            edit(exercise).unwrap_or_else(|_| session::exit(1));
         }
 
-        Subcommands::Hint { name } => {
-            let exercise = find_exercise(&name, &exercises);
+        Subcommands::History { name } => {
+            let exercise = find_exercise(&name, &exercises, &state);
 
-            println!("{}", exercise.hint);
+            history(exercise).unwrap_or_else(|_| session::exit(1));
         }
 
-        Subcommands::Verify => {
-            verify(&exercises, (0, exercises.len()), verbose, false)
-                .unwrap_or_else(|_| std::process::exit(1));
+        Subcommands::Restore { name, snapshot } => {
+            let exercise = find_exercise(&name, &exercises, &state);
+
+            restore(exercise, snapshot).unwrap_or_else(|_| session::exit(1));
+        }
+
+        Subcommands::Solution { name, diff } => {
+            let exercise = find_exercise(&name, &exercises, &state);
+
+            solution(exercise, &state, diff).unwrap_or_else(|_| session::exit(1));
+        }
+
+        Subcommands::Hint { name, level, next } => {
+            let exercise = find_exercise(&name, &exercises, &state);
+            let level = match (level, next) {
+                (Some(level), _) => HintLevel::Level(level),
+                (None, true) => HintLevel::Next,
+                (None, false) => HintLevel::Revealed,
+            };
+
+            hint(exercise, &mut state, level).unwrap_or_else(|_| session::exit(1));
+        }
+
+        Subcommands::Verify {
+            jobs,
+            format,
+            junit,
+        } => {
+            let options = VerifyOptions {
+                verbose,
+                success_hints: false,
+                jobs: jobs.get(),
+                format,
+                junit,
+                history: false,
+            };
+            verify(&exercises, (0, exercises.len()), &options, &mut state)
+                .unwrap_or_else(|_| session::exit(1));
         }
 
         Subcommands::Lsp => {
@@ -208,7 +490,7 @@ fn main() {
                 .get_sysroot_src()
                 .expect("Couldn't find toolchain path, do you have `rustc` installed?");
             project
-                .exercises_to_json()
+                .exercises_to_json(&exercises)
                 .expect("Couldn't parse rustlings exercises files");
 
             if project.crates.is_empty() {
@@ -221,35 +503,59 @@ fn main() {
             }
         }
 
-        Subcommands::Watch { success_hints } => match watch(&exercises, verbose, success_hints) {
-            Err(e) => {
-                println!(
-                    "Error: Could not watch your progress. Error message was {:?}.",
-                    e
-                );
-                println!("Most likely you've run out of disk space or your 'inotify limit' has been reached.");
-                std::process::exit(1);
-            }
-            Ok(WatchStatus::Finished) => {
-                println!(
-                    "{emoji} All exercises completed! {emoji}",
-                    emoji = Emoji("🎉", "★")
-                );
-                println!("\n{FENISH_LINE}\n");
-            }
-            Ok(WatchStatus::Unfinished) => {
-                println!("We hope you're enjoying learning about Rust!");
-                println!("If you want to continue working on the exercises at a later point, you can simply run `rustlings watch` again");
+        Subcommands::New {
+            name,
+            category,
+            mode,
+            manifest,
+        } => {
+            new_exercise(&name, &category, mode, &manifest, &exercises)
+                .unwrap_or_else(|_| session::exit(1));
+        }
+
+        Subcommands::CheckManifest => unreachable!("checked before loading the exercises"),
+
+        Subcommands::Config => {
+            config::show().unwrap_or_else(|_| session::exit(1));
+        }
+
+        Subcommands::Clean { dry_run } => {
+            clean(&exercises, dry_run).unwrap_or_else(|_| session::exit(1));
+        }
+
+        Subcommands::Watch { .. } => {
+            match watch(&exercises, verbose, config.success_hints(), state) {
+                Err(e) => {
+                    println!(
+                        "Error: Could not watch your progress. Error message was {:?}.",
+                        e
+                    );
+                    println!("Most likely you've run out of disk space or your 'inotify limit' has been reached.");
+                    session::exit(1);
+                }
+                Ok(WatchStatus::Finished) => {
+                    println!(
+                        "{emoji} All exercises completed! {emoji}",
+                        emoji = Emoji("🎉", "★")
+                    );
+                    println!("\n{FENISH_LINE}\n");
+                }
+                Ok(WatchStatus::Unfinished) => {
+                    println!("We hope you're enjoying learning about Rust!");
+                    println!("If you want to continue working on the exercises at a later point, you can simply run `rustlings watch` again");
+                }
             }
-        },
+        }
     }
 }
 
 fn spawn_watch_shell(
-    failed_exercise_hint: &Arc<Mutex<Option<String>>>,
+    failed_exercise: &Arc<Mutex<Option<Exercise>>>,
+    state: Arc<Mutex<StateFile>>,
     should_quit: Arc<AtomicBool>,
+    editing: Arc<AtomicBool>,
 ) {
-    let failed_exercise_hint = Arc::clone(failed_exercise_hint);
+    let failed_exercise = Arc::clone(failed_exercise);
     println!("Welcome to watch mode! You can type 'help' to get an overview of the commands you can use here.");
     thread::spawn(move || loop {
         let mut input = String::new();
@@ -257,8 +563,20 @@ fn spawn_watch_shell(
             Ok(_) => {
                 let input = input.trim();
                 if input == "hint" {
-                    if let Some(hint) = &*failed_exercise_hint.lock().unwrap() {
-                        println!("{hint}");
+                    if let Some(exercise) = &*failed_exercise.lock().unwrap() {
+                        let _ = hint(exercise, &mut state.lock().unwrap(), HintLevel::Next);
+                    }
+                } else if input == "edit" || input == "open" {
+                    // Clone the exercise rather than holding the lock while the editor is open
+                    let exercise = failed_exercise.lock().unwrap().clone();
+                    match exercise {
+                        Some(exercise) => {
+                            // Watch mode holds off checking until the editor exits
+                            editing.store(true, Ordering::SeqCst);
+                            let _ = edit(&exercise);
+                            editing.store(false, Ordering::SeqCst);
+                        }
+                        None => println!("There's no exercise to edit right now"),
                     }
                 } else if input == "clear" {
                     println!("\x1B[2J\x1B[1;1H");
@@ -267,7 +585,8 @@ fn spawn_watch_shell(
                     println!("Bye!");
                 } else if input.eq("help") {
                     println!("Commands available to you in watch mode:");
-                    println!("  hint   - prints the current exercise's hint");
+                    println!("  hint   - prints the current exercise's next hint");
+                    println!("  edit   - opens the current exercise in your editor (or `open`)");
                     println!("  clear  - clears the screen");
                     println!("  quit   - quits watch mode");
                     println!("  !<cmd> - executes a command, like `!rustc --explain E0381`");
@@ -291,15 +610,15 @@ fn spawn_watch_shell(
     });
 }
 
-fn find_exercise<'a>(name: &str, exercises: &'a [Exercise]) -> &'a Exercise {
+fn find_exercise<'a>(name: &str, exercises: &'a [Exercise], state: &StateFile) -> &'a Exercise {
     if name.eq("next") {
         exercises
             .iter()
-            .find(|e| !e.looks_done())
+            .find(|e| !state.is_done(e))
             .unwrap_or_else(|| {
                 println!("🎉 Congratulations! You have done all the exercises!");
                 println!("🔚 There are no more exercises to do next!");
-                std::process::exit(1)
+                session::exit(1)
             })
     } else {
         exercises
@@ -307,11 +626,24 @@ fn find_exercise<'a>(name: &str, exercises: &'a [Exercise]) -> &'a Exercise {
             .find(|e| e.name == name)
             .unwrap_or_else(|| {
                 println!("No exercise found for '{name}'!");
-                std::process::exit(1)
+                session::exit(1)
             })
     }
 }
 
+// Whether the changed file is the exercise's file,
+// or one of the sources in the package of a Cargo exercise
+fn owns(exercise: &Exercise, filepath: &Path) -> bool {
+    match exercise.mode {
+        Mode::Cargo => exercise
+            .sources()
+            .iter()
+            .filter_map(|source| source.canonicalize().ok())
+            .any(|source| source == filepath),
+        _ => filepath.ends_with(&exercise.path),
+    }
+}
+
 enum WatchStatus {
     Finished,
     Unfinished,
@@ -321,61 +653,75 @@ fn watch(
     exercises: &[Exercise],
     verbose: bool,
     success_hints: bool,
+    state: StateFile,
 ) -> notify::Result<WatchStatus> {
     /* Clears the terminal with an ANSI escape code.
     Works in UNIX and newer Windows terminals. */
     fn clear_screen() {
-        println!("\x1Bc");
+        if config::get().clear_screen() {
+            println!("\x1Bc");
+        }
     }
 
     let (tx, rx) = channel();
     let should_quit = Arc::new(AtomicBool::new(false));
+    let editing = Arc::new(AtomicBool::new(false));
 
     let mut watcher: RecommendedWatcher = Watcher::new(tx, Duration::from_secs(1))?;
     watcher.watch(Path::new("./exercises"), RecursiveMode::Recursive)?;
+    // Exercise packs may keep their exercises elsewhere
+    let pack_packages = exercises
+        .iter()
+        .filter(|e| !e.path.starts_with("exercises") && matches!(e.mode, Mode::Cargo));
+    for package in pack_packages {
+        watcher.watch(&package.path, RecursiveMode::Recursive)?;
+    }
+    for dir in exercise::pack_dirs(exercises) {
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+    }
 
     clear_screen();
 
-    let to_owned_hint = |t: &Exercise| t.hint.to_owned();
-    let failed_exercise_hint = match verify(
-        exercises.iter(),
-        (0, exercises.len()),
+    let options = VerifyOptions {
         verbose,
         success_hints,
+        jobs: 1,
+        format: Format::Human,
+        junit: None,
+        history: true,
+    };
+    // The watch shell reveals hints, so it shares the progress store
+    let state = Arc::new(Mutex::new(state));
+    let failed_exercise = match verify(
+        exercises.iter(),
+        (0, exercises.len()),
+        &options,
+        &mut state.lock().unwrap(),
     ) {
         Ok(_) => return Ok(WatchStatus::Finished),
-        Err(exercise) => Arc::new(Mutex::new(Some(to_owned_hint(exercise)))),
+        Err(exercise) => Arc::new(Mutex::new(Some(exercise.clone()))),
     };
-    spawn_watch_shell(&failed_exercise_hint, Arc::clone(&should_quit));
+    spawn_watch_shell(
+        &failed_exercise,
+        Arc::clone(&state),
+        Arc::clone(&should_quit),
+        Arc::clone(&editing),
+    );
+    // While the watch shell has an editor open, changes are only checked
+    // once it exits, so that nothing is printed over the editor
+    let mut unchecked: Option<PathBuf> = None;
     loop {
         match rx.recv_timeout(Duration::from_secs(1)) {
             Ok(event) => match event {
-                DebouncedEvent::Create(b) | DebouncedEvent::Chmod(b) | DebouncedEvent::Write(b) => {
-                    if b.extension() == Some(OsStr::new("rs")) && b.exists() {
-                        let filepath = b.as_path().canonicalize().unwrap();
-                        let pending_exercises = exercises
-                            .iter()
-                            .find(|e| filepath.ends_with(&e.path))
-                            .into_iter()
-                            .chain(
-                                exercises
-                                    .iter()
-                                    .filter(|e| !e.looks_done() && !filepath.ends_with(&e.path)),
-                            );
-                        let num_done = exercises.iter().filter(|e| e.looks_done()).count();
-                        clear_screen();
-                        match verify(
-                            pending_exercises,
-                            (num_done, exercises.len()),
-                            verbose,
-                            success_hints,
-                        ) {
-                            Ok(_) => return Ok(WatchStatus::Finished),
-                            Err(exercise) => {
-                                let mut failed_exercise_hint = failed_exercise_hint.lock().unwrap();
-                                *failed_exercise_hint = Some(to_owned_hint(exercise));
-                            }
-                        }
+                DebouncedEvent::Create(b) | DebouncedEvent::Chmod(b) | DebouncedEvent::Write(b)
+                    if b.exists() =>
+                {
+                    let filepath = b.as_path().canonicalize().unwrap();
+                    // Any source of a Cargo exercise counts, Cargo.toml included
+                    if exercises.iter().any(|e| owns(e, &filepath))
+                        || b.extension() == Some(OsStr::new("rs"))
+                    {
+                        unchecked = Some(filepath);
                     }
                 }
                 _ => {}
@@ -385,6 +731,33 @@ fn watch(
             }
             Err(e) => println!("watch error: {e:?}"),
         }
+        if !editing.load(Ordering::SeqCst) {
+            if let Some(filepath) = unchecked.take() {
+                let changed = exercises.iter().find(|e| owns(e, &filepath));
+                let mut state = state.lock().unwrap();
+                let pending_exercises: Vec<&Exercise> = changed
+                    .into_iter()
+                    .chain(
+                        exercises
+                            .iter()
+                            .filter(|e| !state.is_done(e) && !owns(e, &filepath)),
+                    )
+                    .collect();
+                let num_done = exercises.iter().filter(|e| state.is_done(e)).count();
+                clear_screen();
+                match verify(
+                    pending_exercises,
+                    (num_done, exercises.len()),
+                    &options,
+                    &mut state,
+                ) {
+                    Ok(_) => return Ok(WatchStatus::Finished),
+                    Err(exercise) => {
+                        *failed_exercise.lock().unwrap() = Some(exercise.clone());
+                    }
+                }
+            }
+        }
         // Check if we need to exit
         if should_quit.load(Ordering::SeqCst) {
             return Ok(WatchStatus::Unfinished);
@@ -393,7 +766,7 @@ fn watch(
 }
 
 fn rustc_exists() -> bool {
-    Command::new("rustc")
+    exercise::rustc()
         .args(["--version"])
         .stdout(Stdio::null())
         .spawn()
@@ -0,0 +1,59 @@
+use crate::exercise::{Exercise, ExerciseList};
+use glob::glob;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const MAIN_MANIFEST: &str = "info.toml";
+const PACK_PATTERN: &str = "info.d/*.toml";
+
+/// Load every exercise rustlings knows about, in the order they're done:
+/// first `info.toml`, then the packs in `info.d/` sorted by file name,
+/// and finally the extra packs given on the command line, in that order.
+/// Two exercises with the same name are an error.
+pub fn load(extra_packs: &[PathBuf]) -> Result<Vec<Exercise>, String> {
+    let mut exercises = Vec::new();
+    let mut defined_in: HashMap<String, PathBuf> = HashMap::new();
+
+    for manifest in manifests(extra_packs)? {
+        for exercise in parse(&manifest)? {
+            if let Some(first) = defined_in.get(&exercise.name) {
+                return Err(format!(
+                    "The exercise `{}` in {} is already defined in {}",
+                    exercise.name,
+                    manifest.display(),
+                    first.display()
+                ));
+            }
+            defined_in.insert(exercise.name.clone(), manifest.clone());
+            exercises.push(exercise);
+        }
+    }
+    Ok(exercises)
+}
+
+/// The manifest files to load the exercises from, in order.
+pub fn manifests(extra_packs: &[PathBuf]) -> Result<Vec<PathBuf>, String> {
+    let mut manifests = vec![PathBuf::from(MAIN_MANIFEST)];
+    let mut packs = glob(PACK_PATTERN)
+        .expect("The pack pattern should be valid")
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read the pack directory: {e}"))?;
+    packs.sort();
+    manifests.extend(packs);
+    manifests.extend(extra_packs.iter().cloned());
+    Ok(manifests)
+}
+
+/// Parse the exercises of a single manifest, remembering where they came from.
+pub fn parse(manifest: &Path) -> Result<Vec<Exercise>, String> {
+    let toml_str = fs::read_to_string(manifest)
+        .map_err(|e| format!("Failed to read {}: {e}", manifest.display()))?;
+    let mut exercises = toml::from_str::<ExerciseList>(&toml_str)
+        .map_err(|e| format!("Failed to parse {}: {e}", manifest.display()))?
+        .exercises;
+    for exercise in &mut exercises {
+        exercise.manifest = manifest.to_path_buf();
+    }
+    Ok(exercises)
+}
@@ -0,0 +1,105 @@
+use crate::exercise::{self, Exercise, Mode, SourceFiles};
+use crate::state::now;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+const PRISTINE_DIR: &str = ".rustlings/pristine";
+const BACKUP_DIR: &str = ".rustlings/backup";
+
+// The exercises rustlings was built with, see build.rs
+include!(concat!(env!("OUT_DIR"), "/originals.rs"));
+
+// The original the exercise was built into rustlings with. Only exercises
+// of the checkout rustlings was built from have one, found by the name and
+// the path of their entry, so that other exercises, like the ones `new`
+// adds, never take their place
+fn built_in(exercise: &Exercise) -> Option<SourceFiles> {
+    let path = exercise
+        .path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    let (_, _, files) = ORIGINALS
+        .iter()
+        .find(|(name, original, _)| *name == exercise.name && *original == path)?;
+    let files = files
+        .iter()
+        .map(|(relative, contents)| (PathBuf::from(relative), contents.to_vec()))
+        .collect();
+    Some(files)
+}
+
+// The untouched original of the exercise, named after the exercise
+// because exercises of packs may live anywhere
+fn pristine_path(exercise: &Exercise) -> PathBuf {
+    PathBuf::from(PRISTINE_DIR).join(format!("{}{}", exercise.name, extension(exercise)))
+}
+
+// Copies of Cargo exercises are directories, the others are Rust files
+fn extension(exercise: &Exercise) -> &'static str {
+    match exercise.mode {
+        Mode::Cargo => "",
+        _ => ".rs",
+    }
+}
+
+/// Keep a pristine copy of every exercise rustlings sees for the first time,
+/// so that `reset` works without git and even after edits were committed.
+/// Exercises that were built into rustlings don't need one.
+pub fn snapshot(exercises: &[Exercise]) {
+    let mut failed = Vec::new();
+    for exercise in exercises {
+        let pristine = pristine_path(exercise);
+        if pristine.exists() || !exercise.path.exists() || built_in(exercise).is_some() {
+            continue;
+        }
+        let copied = exercise::read_files(&exercise.path)
+            .and_then(|files| exercise::write_files(&pristine, &files));
+        if let Err(e) = copied {
+            failed.push(format!("{} ({e})", exercise.name));
+        }
+    }
+    if !failed.is_empty() {
+        warn!(
+            "Failed to keep pristine copies of these exercises: {}",
+            failed.join(", ")
+        );
+    }
+}
+
+/// Restore the pristine copy of the exercise. If it was edited, the current
+/// version is backed up first and the path of the backup is returned.
+pub fn restore(exercise: &Exercise) -> io::Result<Option<PathBuf>> {
+    let original = match built_in(exercise) {
+        Some(original) => original,
+        None => exercise::read_files(&pristine_path(exercise))
+            .map_err(|e| io::Error::new(e.kind(), format!("no original copy was kept ({e})")))?,
+    };
+    let current = match exercise::read_files(&exercise.path) {
+        Ok(current) => Some(current),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e),
+    };
+
+    let mut backup = None;
+    if let Some(current) = current.filter(|current| *current != original) {
+        fs::create_dir_all(BACKUP_DIR)?;
+        let stamp = now();
+        let extension = extension(exercise);
+        let mut path =
+            PathBuf::from(BACKUP_DIR).join(format!("{}-{stamp}{extension}", exercise.name));
+        for n in 1.. {
+            if !path.exists() {
+                break;
+            }
+            path =
+                PathBuf::from(BACKUP_DIR).join(format!("{}-{stamp}-{n}{extension}", exercise.name));
+        }
+        exercise::write_files(&path, &current)?;
+        backup = Some(path);
+    }
+    exercise::write_files(&exercise.path, &original)?;
+    Ok(backup)
+}
@@ -1,9 +1,10 @@
+use crate::exercise::{self, Edition, Exercise};
 use glob::glob;
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::error::Error;
-use std::path::PathBuf;
-use std::process::Command;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
 
 /// Contains the structure of resulting rust-project.json file
 /// and functions to build the data required to create the file
@@ -38,16 +39,50 @@ impl RustAnalyzerProject {
         Ok(())
     }
 
-    /// If path contains .rs extension, add a crate to `rust-project.json`
-    fn path_to_json(&mut self, path: PathBuf) -> Result<(), Box<dyn Error>> {
+    /// If path contains .rs extension, add a crate to `rust-project.json`.
+    /// In the package of a Cargo exercise, only the library and binary
+    /// roots are crates, the other files are their modules
+    fn path_to_json(
+        &mut self,
+        path: PathBuf,
+        exercises: &[Exercise],
+    ) -> Result<(), Box<dyn Error>> {
+        let path = normalize(&path);
+        let package = path
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.starts_with("exercises"))
+            .find(|dir| dir.join("Cargo.toml").is_file());
+        if let Some(package) = package {
+            let src = package.join("src");
+            if path != src.join("lib.rs") && path != src.join("main.rs") {
+                return Ok(());
+            }
+        }
         if let Some(ext) = path.extension() {
             if ext == "rs" {
+                let root = package.unwrap_or(&path);
+                let exercise = exercises.iter().find(|e| normalize(&e.path) == root);
+                let edition = match package {
+                    Some(package) => package_edition(package),
+                    None => exercise.map(Exercise::edition).unwrap_or_default(),
+                };
+                // This allows rust_analyzer to work inside #[test] blocks
+                let mut cfg = vec!["test".to_string()];
+                if let Some(exercise) = exercise {
+                    cfg.extend(
+                        exercise
+                            .rustc_args
+                            .windows(2)
+                            .filter(|pair| pair[0] == "--cfg")
+                            .map(|pair| pair[1].clone()),
+                    );
+                }
                 self.crates.push(Crate {
                     root_module: path.display().to_string(),
-                    edition: "2021".to_string(),
+                    edition: edition.to_string(),
                     deps: Vec::new(),
-                    // This allows rust_analyzer to work inside #[test] blocks
-                    cfg: vec!["test".to_string()],
+                    cfg,
                 })
             }
         }
@@ -57,10 +92,11 @@ impl RustAnalyzerProject {
 
     /// Parse the exercises folder for .rs files, any matches will create
     /// a new `crate` in rust-project.json which allows rust-analyzer to
-    /// treat it like a normal binary
-    pub fn exercises_to_json(&mut self) -> Result<(), Box<dyn Error>> {
+    /// treat it like a normal binary. The editions and cfgs of the
+    /// crates come from the exercises
+    pub fn exercises_to_json(&mut self, exercises: &[Exercise]) -> Result<(), Box<dyn Error>> {
         for path in glob("./exercises/**/*")? {
-            self.path_to_json(path?)?;
+            self.path_to_json(path?, exercises)?;
         }
         Ok(())
     }
@@ -73,7 +109,7 @@ impl RustAnalyzerProject {
             return Ok(());
         }
 
-        let toolchain = Command::new("rustc")
+        let toolchain = exercise::rustc()
             .arg("--print")
             .arg("sysroot")
             .output()?
@@ -97,3 +133,27 @@ impl RustAnalyzerProject {
         Ok(())
     }
 }
+
+// The edition in the Cargo.toml of a package, which Cargo
+// defaults to 2015 when there is none
+fn package_edition(package: &Path) -> Edition {
+    fs::read_to_string(package.join("Cargo.toml"))
+        .ok()
+        .and_then(|manifest| manifest.parse::<toml::Table>().ok())
+        .and_then(|manifest| {
+            manifest
+                .get("package")?
+                .get("edition")?
+                .clone()
+                .try_into()
+                .ok()
+        })
+        .unwrap_or(Edition::E2015)
+}
+
+// Paths in manifests may or may not start with `./`
+pub fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| *c != Component::CurDir)
+        .collect()
+}
@@ -0,0 +1,231 @@
+use crate::exercise::{Difficulty, Exercise, Mode};
+use crate::session;
+use crate::util::strip_colors;
+use crate::verify::Outcome;
+use clap::ValueEnum;
+use serde::{Serialize, Serializer};
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// The output format of `list` and `verify`
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// Colored text for humans
+    #[default]
+    Human,
+    /// A JSON array with one object per exercise
+    Json,
+    /// CSV with a header row and one row per exercise
+    Csv,
+}
+
+/// A row of `rustlings list --format json|csv`.
+/// Fields are only ever added to this schema, never renamed or removed.
+#[derive(Serialize)]
+pub struct ListEntry<'a> {
+    pub name: &'a str,
+    pub path: &'a Path,
+    pub mode: Mode,
+    /// Either `done` or `pending`
+    pub status: &'static str,
+    /// The directory under `exercises/` the exercise lives in, if any
+    pub category: Option<&'a str>,
+    /// One of `easy`, `medium` or `hard`, if known
+    pub difficulty: Option<Difficulty>,
+    /// The sections of the Rust book the exercise is about, if any
+    pub book_chapter: Option<&'a str>,
+    /// Comma-separated labels, like `quiz`
+    pub tags: String,
+    /// Comma-separated Rust concepts the exercise practices
+    pub topics: String,
+}
+
+impl<'a> ListEntry<'a> {
+    pub fn new(exercise: &'a Exercise, done: bool) -> Self {
+        ListEntry {
+            name: &exercise.name,
+            path: &exercise.path,
+            mode: exercise.mode,
+            status: if done { "done" } else { "pending" },
+            category: exercise.category(),
+            difficulty: exercise.difficulty,
+            book_chapter: exercise.book_chapter.as_deref(),
+            tags: exercise.tags.join(","),
+            topics: exercise.topics.join(","),
+        }
+    }
+}
+
+/// A row of `rustlings verify --format json|csv`.
+/// Fields are only ever added to this schema, never renamed or removed.
+#[derive(Serialize)]
+pub struct VerifyEntry<'a> {
+    pub name: &'a str,
+    pub path: &'a Path,
+    pub mode: Mode,
+    /// The directory under `exercises/` the exercise lives in, if any
+    pub category: Option<&'a str>,
+    /// One of `passed`, `compile_failed`, `run_failed`, `timed_out`,
+    /// `memory_exceeded`, `cpu_time_exceeded`, `wrong_output`,
+    /// `compiled_unexpectedly`, `wrong_error` or `unformatted`
+    #[serde(serialize_with = "outcome_name")]
+    pub outcome: &'a Outcome,
+    /// Whether the exercise passed and its `I AM NOT DONE` marker is removed
+    pub done: bool,
+    /// The output of the compiler if compilation failed,
+    /// otherwise the output of the binary or test harness,
+    /// without any terminal color codes
+    pub stdout: String,
+    pub stderr: String,
+    pub duration_ms: u128,
+}
+
+impl<'a> VerifyEntry<'a> {
+    pub fn new(
+        exercise: &'a Exercise,
+        outcome: &'a Outcome,
+        duration: Duration,
+        done: bool,
+    ) -> Self {
+        let output = match outcome {
+            Outcome::CompileFailed(output)
+            | Outcome::RunFailed(output)
+            | Outcome::TimedOut(output)
+            | Outcome::LimitExceeded(_, output)
+            | Outcome::WrongOutput(output)
+            | Outcome::WrongError(output)
+            | Outcome::Unformatted(output) => Some(output),
+            Outcome::CompiledUnexpectedly => None,
+            Outcome::Passed(output) => output.as_ref(),
+        };
+        VerifyEntry {
+            name: &exercise.name,
+            path: &exercise.path,
+            mode: exercise.mode,
+            category: exercise.category(),
+            outcome,
+            done,
+            // Compiler output is always colored, which is noise in a report
+            stdout: output.map_or_else(String::new, |o| strip_colors(&o.stdout)),
+            stderr: output.map_or_else(String::new, |o| strip_colors(&o.stderr)),
+            duration_ms: duration.as_millis(),
+        }
+    }
+}
+
+// Outcomes are reported by name, not with their output,
+// which the rows already carry
+fn outcome_name<S: Serializer>(outcome: &&Outcome, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(outcome.name())
+}
+
+/// Write the rows to stdout in the given machine-readable format.
+/// Like `list`, a closed pipe ends the process successfully.
+pub fn write_rows<T: Serialize>(format: Format, rows: &[T]) {
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    let result = match format {
+        Format::Json => serde_json::to_writer_pretty(&mut handle, rows)
+            .map_err(io::Error::from)
+            .and_then(|_| writeln!(handle)),
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(&mut handle);
+            rows.iter()
+                .try_for_each(|row| writer.serialize(row))
+                .map_err(io::Error::from)
+                .and_then(|_| writer.flush())
+        }
+        Format::Human => unreachable!("human output is not made of rows"),
+    };
+    if let Err(e) = result {
+        match e.kind() {
+            io::ErrorKind::BrokenPipe => session::exit(0),
+            _ => session::exit(1),
+        }
+    }
+}
+
+/// Write a JUnit XML report with one `<testcase>` per checked exercise.
+/// Exercises that aren't done yet are reported as failures, with the
+/// compiler errors or the output of the exercise as the failure body.
+pub fn write_junit(path: &Path, rows: &[VerifyEntry]) -> io::Result<()> {
+    let failures = rows.iter().filter(|row| !row.done).count();
+    let time = rows.iter().map(|row| row.duration_ms).sum::<u128>() as f64 / 1000.0;
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        r#"<testsuites name="rustlings" tests="{}" failures="{failures}" time="{time:.3}">"#,
+        rows.len()
+    );
+    let _ = writeln!(
+        xml,
+        r#"  <testsuite name="rustlings" tests="{}" failures="{failures}" time="{time:.3}">"#,
+        rows.len()
+    );
+    for row in rows {
+        let _ = write!(
+            xml,
+            r#"    <testcase name="{}" classname="{}" file="{}" time="{:.3}""#,
+            escape_xml(row.name),
+            escape_xml(row.category.unwrap_or("exercises")),
+            escape_xml(&row.path.display().to_string()),
+            row.duration_ms as f64 / 1000.0,
+        );
+        if row.done {
+            xml.push_str("/>\n");
+            continue;
+        }
+        let (message, body) = match row.outcome {
+            Outcome::CompileFailed(_) => ("Compilation failed", row.stderr.clone()),
+            Outcome::RunFailed(_) => ("Running failed", format!("{}{}", row.stdout, row.stderr)),
+            Outcome::TimedOut(_) => ("Timed out", format!("{}{}", row.stdout, row.stderr)),
+            Outcome::LimitExceeded(..) => (
+                "Resource limit exceeded",
+                format!("{}{}", row.stdout, row.stderr),
+            ),
+            Outcome::WrongOutput(_) => ("Unexpected output", row.stdout.clone()),
+            Outcome::CompiledUnexpectedly => ("Compiled, but should fail to", String::new()),
+            Outcome::WrongError(_) => ("Failed with an unexpected error", row.stderr.clone()),
+            Outcome::Unformatted(_) => ("Not formatted with rustfmt", row.stdout.clone()),
+            Outcome::Passed(_) => (
+                "The `I AM NOT DONE` comment is still there",
+                row.stdout.clone(),
+            ),
+        };
+        let kind = match row.outcome {
+            Outcome::Passed(_) => "not_done",
+            outcome => outcome.name(),
+        };
+        let _ = writeln!(
+            xml,
+            ">\n      <failure type=\"{kind}\" message=\"{}\">{}</failure>\n    </testcase>",
+            escape_xml(message),
+            escape_xml(&body),
+        );
+    }
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    fs::write(path, xml)
+}
+
+// Escape text for use in XML content and attributes,
+// dropping the control characters XML can't represent at all
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
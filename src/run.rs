@@ -1,8 +1,11 @@
-use std::process::Command;
 use std::time::Duration;
 
-use crate::exercise::{Exercise, Mode};
-use crate::verify::test;
+use crate::exercise::{Exercise, Mode, RunFailure};
+use crate::pristine;
+use crate::verify::{
+    check_formatting, print_limit_exceeded, print_timed_out, print_unformatted, print_wrong_output,
+    test, Outcome,
+};
 use indicatif::ProgressBar;
 
 // Invoke the rust compiler on the path of the given exercise,
@@ -11,23 +14,27 @@ use indicatif::ProgressBar;
 // the output from the test harnesses (if the mode of the exercise is test)
 pub fn run(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
     match exercise.mode {
-        Mode::Test => test(exercise, verbose)?,
+        Mode::Test | Mode::CompileFail | Mode::Cargo => test(exercise, verbose)?,
         Mode::Compile => compile_and_run(exercise)?,
         Mode::Clippy => compile_and_run(exercise)?,
     }
     Ok(())
 }
 
-// Resets the exercise by stashing the changes.
+// Resets the exercise to its pristine copy, backing up the current version.
 pub fn reset(exercise: &Exercise) -> Result<(), ()> {
-    let command = Command::new("git")
-        .args(["stash", "--"])
-        .arg(&exercise.path)
-        .spawn();
-
-    match command {
-        Ok(_) => Ok(()),
-        Err(_) => Err(()),
+    match pristine::restore(exercise) {
+        Ok(backup) => {
+            success!("Reset {}", exercise);
+            if let Some(backup) = backup {
+                println!("Your version was saved to {}", backup.display());
+            }
+            Ok(())
+        }
+        Err(e) => {
+            warn!("Failed to reset {}", format!("{exercise}: {e}"));
+            Err(())
+        }
     }
 }
 
@@ -60,15 +67,32 @@ fn compile_and_run(exercise: &Exercise) -> Result<(), ()> {
     match result {
         Ok(output) => {
             println!("{}", output.stdout);
+            // Like verify, exercises that require formatting don't pass unformatted
+            if let Outcome::Unformatted(diff) = check_formatting(exercise, Outcome::Passed(None)) {
+                print_unformatted(exercise, &diff);
+                return Err(());
+            }
             success!("Successfully ran {}", exercise);
             Ok(())
         }
-        Err(output) => {
+        Err(RunFailure::Failed(output)) => {
             println!("{}", output.stdout);
             println!("{}", output.stderr);
 
             warn!("Ran {} with errors", exercise);
             Err(())
         }
+        Err(RunFailure::TimedOut(output)) => {
+            print_timed_out(exercise, &output);
+            Err(())
+        }
+        Err(RunFailure::LimitExceeded(resource, output)) => {
+            print_limit_exceeded(exercise, resource, &output);
+            Err(())
+        }
+        Err(RunFailure::WrongOutput(output)) => {
+            print_wrong_output(exercise, &output);
+            Err(())
+        }
     }
 }
@@ -0,0 +1,68 @@
+use crate::exercise::{Exercise, ExerciseOutput, Mode};
+use std::process::Command;
+
+// rustfmt for single files, or `cargo fmt` for the package of a Cargo
+// exercise, which takes the edition from its Cargo.toml. Like compiling,
+// formatting uses the exercise's toolchain and edition
+fn rustfmt(exercise: &Exercise) -> Command {
+    let program = match exercise.mode {
+        Mode::Cargo => "cargo",
+        _ => "rustfmt",
+    };
+    let mut command = Command::new(program);
+    if let Some(toolchain) = exercise.active_toolchain() {
+        command.arg(format!("+{toolchain}"));
+    }
+    match exercise.mode {
+        Mode::Cargo => command
+            .args(["fmt", "--manifest-path"])
+            .arg(exercise.path.join("Cargo.toml"))
+            .arg("--"),
+        _ => command
+            .args(["--edition", &exercise.edition().to_string()])
+            .arg(&exercise.path),
+    };
+    command
+}
+
+/// Check whether the exercise is formatted the way rustfmt would format it.
+/// If it isn't, the output holds the diff rustfmt suggests.
+pub fn check(exercise: &Exercise) -> Result<(), ExerciseOutput> {
+    let output = rustfmt(exercise)
+        .args(["--check", "--color", "never"])
+        .output();
+    match output {
+        Ok(output) if output.status.success() => Ok(()),
+        Ok(output) => Err(ExerciseOutput {
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        }),
+        Err(e) => Err(ExerciseOutput {
+            stdout: String::new(),
+            stderr: format!(
+                "Failed to run rustfmt: {e}\nInstall it with `rustup component add rustfmt`"
+            ),
+        }),
+    }
+}
+
+// Format the exercise in place with rustfmt
+pub fn format(exercise: &Exercise) -> Result<(), ()> {
+    let output = rustfmt(exercise).output();
+    match output {
+        Ok(output) if output.status.success() => {
+            success!("Formatted {}", exercise);
+            Ok(())
+        }
+        Ok(output) => {
+            warn!("Failed to format {}! Here's the output:", exercise);
+            println!("{}", String::from_utf8_lossy(&output.stderr));
+            Err(())
+        }
+        Err(e) => {
+            warn!("Failed to run rustfmt: {}", e);
+            println!("Install it with `rustup component add rustfmt`");
+            Err(())
+        }
+    }
+}
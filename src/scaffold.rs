@@ -0,0 +1,320 @@
+use crate::exercise::{Exercise, ExerciseList, Mode};
+use crate::project::RustAnalyzerProject;
+use regex::Regex;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const COMPILE_TEMPLATE: &str = r#"// {name}.rs
+//
+// TODO: Explain what this exercise is about.
+//
+// Execute `rustlings hint {name}` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+fn main() {
+    // TODO: Write the exercise.
+}
+"#;
+
+const TEST_TEMPLATE: &str = r#"// {name}.rs
+//
+// TODO: Explain what this exercise is about.
+//
+// Execute `rustlings hint {name}` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn {name}_works() {
+        todo!();
+    }
+}
+"#;
+
+const CLIPPY_TEMPLATE: &str = r#"// {name}.rs
+//
+// TODO: Explain which Clippy lint this exercise is about.
+//
+// Execute `rustlings hint {name}` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+fn main() {
+    // TODO: Write code that Clippy complains about.
+}
+"#;
+
+const COMPILE_FAIL_TEMPLATE: &str = r#"// {name}.rs
+//
+// TODO: Explain which compiler error this exercise is about.
+//
+// Execute `rustlings hint {name}` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+fn main() {
+    // TODO: Write code that fails to compile with the expected error.
+}
+"#;
+
+const CARGO_MANIFEST_TEMPLATE: &str = r#"[package]
+name = "{name}"
+version = "0.0.1"
+edition = "2021"
+publish = false
+
+[dependencies]
+"#;
+
+const CARGO_LIB_TEMPLATE: &str = r#"// {name}
+//
+// TODO: Explain what this exercise is about, and which files of the package
+// the learner has to look at.
+//
+// Execute `rustlings hint {name}` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn {name}_works() {
+        todo!();
+    }
+}
+"#;
+
+const ENTRY_TEMPLATE: &str = r#"[[exercises]]
+name = "{name}"
+path = "{path}"
+mode = "{mode}"
+hint = """
+No hints this time ;)"""
+"#;
+
+// Create the file of a new exercise from the template of its mode, register it
+// in the given manifest next to the other exercises of its category,
+// and regenerate rust-project.json so rust-analyzer picks it up.
+pub fn new_exercise(
+    name: &str,
+    category: &str,
+    mode: Mode,
+    manifest: &Path,
+    exercises: &[Exercise],
+) -> Result<(), ()> {
+    let valid_name = Regex::new(r"^[a-z][a-z0-9_]*$").unwrap();
+    if !valid_name.is_match(name) || !valid_name.is_match(category) {
+        warn!(
+            "Names and categories may only contain lowercase letters, digits and underscores, got {}",
+            format!("`{name}` in `{category}`")
+        );
+        return Err(());
+    }
+    if let Some(existing) = exercises.iter().find(|e| e.name == name) {
+        warn!(
+            "An exercise named {} already exists",
+            format!("`{name}` in {}", existing.manifest.display())
+        );
+        return Err(());
+    }
+
+    // Cargo exercises are package directories
+    let path = match mode {
+        Mode::Cargo => PathBuf::from("exercises").join(category).join(name),
+        _ => PathBuf::from("exercises")
+            .join(category)
+            .join(format!("{name}.rs")),
+    };
+    if path.exists() {
+        warn!("{} already exists", path.display());
+        return Err(());
+    }
+
+    let contents = match fs::read_to_string(manifest) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            warn!("Failed to read {}", format!("{}: {e}", manifest.display()));
+            return Err(());
+        }
+    };
+    let entry = ENTRY_TEMPLATE
+        .replace("{name}", name)
+        .replace("{path}", &path.display().to_string())
+        .replace("{mode}", &mode.to_string());
+    // The author still has to pick the errors, see `check-manifest`
+    let entry = match mode {
+        Mode::CompileFail => entry.replace("\nhint = ", "\nexpected_errors = []\nhint = "),
+        _ => entry,
+    };
+    let updated = insert_entry(&contents, category, &entry);
+    // Refuse to write a manifest rustlings couldn't load anymore
+    if let Err(e) = toml::from_str::<ExerciseList>(&updated) {
+        warn!("Failed to add the exercise to the manifest: {}", e);
+        return Err(());
+    }
+
+    let files = match mode {
+        Mode::Compile => vec![(path.clone(), COMPILE_TEMPLATE)],
+        Mode::Test => vec![(path.clone(), TEST_TEMPLATE)],
+        Mode::Clippy => vec![(path.clone(), CLIPPY_TEMPLATE)],
+        Mode::CompileFail => vec![(path.clone(), COMPILE_FAIL_TEMPLATE)],
+        Mode::Cargo => vec![
+            (path.join("Cargo.toml"), CARGO_MANIFEST_TEMPLATE),
+            (path.join("src").join("lib.rs"), CARGO_LIB_TEMPLATE),
+        ],
+    };
+    let written = files
+        .iter()
+        .try_for_each(|(file, template)| {
+            fs::create_dir_all(file.parent().unwrap())?;
+            fs::write(file, template.replace("{name}", name))
+        })
+        .and_then(|_| fs::write(manifest, updated));
+    if let Err(e) = written {
+        warn!("Failed to create the exercise: {}", e);
+        return Err(());
+    }
+    success!("Created {}", path.display());
+    println!("Registered {name} in {}", manifest.display());
+
+    let mut project = RustAnalyzerProject::new();
+    let regenerated = project
+        .get_sysroot_src()
+        .and_then(|_| project.exercises_to_json(exercises))
+        .and_then(|_| Ok(project.write_to_disk()?));
+    if regenerated.is_err() {
+        warn!(
+            "Failed to regenerate rust-project.json, run {} to try again",
+            "`rustlings lsp`"
+        );
+    }
+    Ok(())
+}
+
+// Insert the entry after the last exercise of the same category,
+// or at the end under a new heading if the category is new.
+// Everything else in the manifest, comments included, is kept as is.
+fn insert_entry(manifest: &str, category: &str, entry: &str) -> String {
+    let lines: Vec<&str> = manifest.lines().collect();
+    let path_regex = Regex::new(r#"^path\s*=\s*"exercises/([^/"]+)/"#).unwrap();
+    let starts: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.trim() == "[[exercises]]")
+        .map(|(i, _)| i)
+        .collect();
+
+    let last_of_category = starts.iter().enumerate().rev().find(|(n, &start)| {
+        let end = starts.get(n + 1).copied().unwrap_or(lines.len());
+        lines[start..end]
+            .iter()
+            .filter_map(|line| path_regex.captures(line))
+            .any(|caps| &caps[1] == category)
+    });
+
+    let mut result = String::with_capacity(manifest.len() + entry.len() + 32);
+    match last_of_category {
+        Some((n, _)) if n + 1 < starts.len() => {
+            // Skip back over the blank lines and heading comments
+            // that introduce the next block
+            let mut at = starts[n + 1];
+            while at > 0 && {
+                let line = lines[at - 1].trim();
+                line.is_empty() || line.starts_with('#')
+            } {
+                at -= 1;
+            }
+            for line in &lines[..at] {
+                result.push_str(line);
+                result.push('\n');
+            }
+            result.push('\n');
+            result.push_str(entry);
+            for line in &lines[at..] {
+                result.push_str(line);
+                result.push('\n');
+            }
+        }
+        found => {
+            result.push_str(manifest);
+            if !manifest.is_empty() && !manifest.ends_with('\n') {
+                result.push('\n');
+            }
+            if found.is_none() {
+                if !manifest.trim().is_empty() {
+                    result.push('\n');
+                }
+                result.push_str(&format!("# {}\n", category.to_uppercase()));
+            }
+            if !manifest.trim().is_empty() || found.is_none() {
+                result.push('\n');
+            }
+            result.push_str(entry);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MANIFEST: &str = r#"# INTRO
+
+[[exercises]]
+name = "intro1"
+path = "exercises/intro/intro1.rs"
+mode = "compile"
+hint = """
+# not a heading
+"""
+
+# VARIABLES
+
+[[exercises]]
+name = "variables1"
+path = "exercises/variables/variables1.rs"
+mode = "compile"
+hint = ""
+"#;
+
+    const ENTRY: &str = "[[exercises]]\nname = \"new\"\n";
+
+    #[test]
+    fn test_insert_after_same_category() {
+        let updated = insert_entry(MANIFEST, "intro", ENTRY);
+        let expected = MANIFEST.replace(
+            "\"\"\"\n\n# VARIABLES",
+            "\"\"\"\n\n[[exercises]]\nname = \"new\"\n\n# VARIABLES",
+        );
+        assert_eq!(updated, expected);
+    }
+
+    #[test]
+    fn test_insert_at_end_of_last_category() {
+        let updated = insert_entry(MANIFEST, "variables", ENTRY);
+        assert_eq!(updated, format!("{MANIFEST}\n{ENTRY}"));
+    }
+
+    #[test]
+    fn test_insert_new_category() {
+        let updated = insert_entry(MANIFEST, "basics", ENTRY);
+        assert_eq!(updated, format!("{MANIFEST}\n# BASICS\n\n{ENTRY}"));
+    }
+
+    #[test]
+    fn test_insert_into_empty_manifest() {
+        let updated = insert_entry("", "basics", ENTRY);
+        assert_eq!(updated, format!("# BASICS\n\n{ENTRY}"));
+    }
+}
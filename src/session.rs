@@ -0,0 +1,181 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{self, Child, Command};
+use std::sync::{Mutex, OnceLock};
+
+const SESSION_PREFIX: &str = "rustlings-";
+// The file that tells a session directory apart from anything else
+// with a similar name, like a checkout of rustlings in the temp directory
+const SESSION_MARKER: &str = ".rustlings-session";
+
+static SESSION_DIR: OnceLock<PathBuf> = OnceLock::new();
+// The process groups of the commands that are running, which don't get
+// the terminal's Ctrl-C and have to be killed when rustlings is interrupted
+static GROUPS: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+/// The directory this run of rustlings puts its build artifacts in,
+/// like `/tmp/rustlings-1234`. It's created the first time it's needed.
+pub fn dir() -> &'static Path {
+    SESSION_DIR.get_or_init(|| {
+        let dir = env::temp_dir().join(format!("{SESSION_PREFIX}{}", process::id()));
+        fs::create_dir_all(&dir).expect("Failed to create a temporary directory");
+        fs::write(dir.join(SESSION_MARKER), "").expect("Failed to create a temporary directory");
+        dir
+    })
+}
+
+/// Remove the session directory, if there is one
+pub fn cleanup() {
+    if let Some(dir) = SESSION_DIR.get() {
+        let _ignored = fs::remove_dir_all(dir);
+    }
+}
+
+/// Clean up and exit the process with the given code
+pub fn exit(code: i32) -> ! {
+    cleanup();
+    process::exit(code)
+}
+
+/// Start the command in a process group of its own, so that it can be
+/// killed together with anything it spawned. The group is killed if
+/// rustlings is interrupted before `release_group` is called for it
+pub fn spawn_group(command: &mut Command) -> io::Result<Child> {
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(command, 0);
+    // Holding the lock while spawning keeps an interrupt from
+    // missing a group that was just started
+    let mut groups = GROUPS.lock().unwrap_or_else(|e| e.into_inner());
+    let child = command.spawn()?;
+    groups.push(child.id());
+    Ok(child)
+}
+
+/// Forget the process group of a child that was waited for
+pub fn release_group(child: &Child) {
+    let mut groups = GROUPS.lock().unwrap_or_else(|e| e.into_inner());
+    groups.retain(|&group| group != child.id());
+}
+
+// Kill every process group that is still running
+#[cfg(unix)]
+fn kill_groups() {
+    let groups = GROUPS.lock().unwrap_or_else(|e| e.into_inner());
+    for &group in groups.iter() {
+        // A negative pid signals the whole process group.
+        // SAFETY: kill has no memory safety requirements
+        unsafe { libc::kill(-(group as libc::pid_t), libc::SIGKILL) };
+    }
+}
+
+/// Cleans up when dropped, so that returning from main or panicking
+/// doesn't leave the session directory behind either
+pub struct Guard;
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        cleanup();
+    }
+}
+
+/// The session directories of earlier runs that are no longer running,
+/// left behind by a crash or a kill
+pub fn stale_dirs() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(env::temp_dir()) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            let Some(pid) = name.to_str().and_then(|n| n.strip_prefix(SESSION_PREFIX)) else {
+                return false;
+            };
+            match pid.parse::<u32>() {
+                Ok(pid) => pid != process::id() && !is_running(pid),
+                Err(_) => false,
+            }
+        })
+        .map(|entry| entry.path())
+        .filter(|path| path.join(SESSION_MARKER).is_file())
+        .collect()
+}
+
+#[cfg(unix)]
+fn is_running(pid: u32) -> bool {
+    // Signal 0 only checks whether the process exists.
+    // SAFETY: kill has no memory safety requirements
+    let exists = unsafe { libc::kill(pid as libc::pid_t, 0) } == 0;
+    // A process of another user can't be signalled, but still exists
+    exists || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+// Without a way to tell, assume every session is still running
+#[cfg(not(unix))]
+fn is_running(_pid: u32) -> bool {
+    true
+}
+
+/// Clean up and kill the running exercises before exiting on Ctrl-C
+/// or when terminated.
+/// The signal handler only writes to a pipe, which a thread
+/// waits on to do the actual work outside of the handler
+#[cfg(unix)]
+pub fn handle_interrupts() {
+    use std::fs::File;
+    use std::io::Read;
+    use std::os::unix::io::FromRawFd;
+    use std::sync::atomic::{AtomicI32, Ordering};
+    use std::thread;
+
+    static SIGNAL_PIPE: AtomicI32 = AtomicI32::new(-1);
+
+    extern "C" fn on_signal(signal: libc::c_int) {
+        let byte = signal as u8;
+        // SAFETY: write is async-signal-safe, and the byte outlives the call
+        unsafe {
+            libc::write(
+                SIGNAL_PIPE.load(Ordering::Relaxed),
+                (&byte as *const u8).cast(),
+                1,
+            );
+        }
+    }
+
+    let mut fds = [0; 2];
+    // SAFETY: pipe writes two file descriptors into the array
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return;
+    }
+    for fd in fds {
+        // Keep the pipe from leaking into the exercises
+        // SAFETY: fcntl only sets a flag of the file descriptor
+        unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) };
+    }
+    SIGNAL_PIPE.store(fds[1], Ordering::Relaxed);
+    // SAFETY: the read end of the pipe isn't owned by anything else
+    let mut pipe = unsafe { File::from_raw_fd(fds[0]) };
+    thread::spawn(move || {
+        let mut signal = [0];
+        if pipe.read_exact(&mut signal).is_ok() {
+            kill_groups();
+            // Like a shell, report death by signal as 128 + its number
+            exit(128 + i32::from(signal[0]));
+        }
+    });
+
+    let handler = on_signal as extern "C" fn(libc::c_int);
+    for signal in [libc::SIGINT, libc::SIGTERM, libc::SIGHUP] {
+        // SAFETY: the handler only calls async-signal-safe functions
+        unsafe {
+            libc::signal(signal, handler as libc::sighandler_t);
+        }
+    }
+}
+
+// Ctrl-C ends the process without cleaning up, so the next
+// `rustlings clean` has to remove the session directory
+#[cfg(not(unix))]
+pub fn handle_interrupts() {}
@@ -0,0 +1,81 @@
+use crate::diff;
+use crate::exercise::{self, Exercise};
+use crate::state::StateFile;
+use console::style;
+use std::fs;
+use std::path::Path;
+
+// Show the reference solution of the given exercise, either as is
+// or as a diff against the learner's own solution.
+// Solutions stay locked until the exercise passed `verify`.
+pub fn solution(exercise: &Exercise, state: &StateFile, show_diff: bool) -> Result<(), ()> {
+    if !state.is_done(exercise) {
+        warn!(
+            "The solution to {} unlocks once you've solved it with `rustlings verify` or `rustlings watch`",
+            exercise.name
+        );
+        return Err(());
+    }
+    let Some(path) = exercise.solution_path() else {
+        warn!("There is no reference solution for {} yet", exercise.name);
+        return Err(());
+    };
+    if path.is_dir() != exercise.path.is_dir() {
+        warn!(
+            "The solution to {} is not laid out like the exercise",
+            exercise.name
+        );
+        return Err(());
+    }
+
+    // A Cargo exercise is compared file by file with its reference package
+    let pairs = if path.is_dir() {
+        let mut files = Vec::new();
+        exercise::collect_sources(&path, &mut files);
+        files.sort();
+        files
+            .into_iter()
+            .map(|file| {
+                let relative = file.strip_prefix(&path).unwrap_or(&file);
+                (exercise.path.join(relative), file.clone())
+            })
+            .collect()
+    } else {
+        vec![(exercise.path.clone(), path)]
+    };
+    for (yours, reference) in pairs {
+        show(exercise, &yours, &reference, show_diff)?;
+    }
+    Ok(())
+}
+
+fn show(exercise: &Exercise, yours: &Path, reference: &Path, show_diff: bool) -> Result<(), ()> {
+    let Ok(reference_text) = fs::read_to_string(reference) else {
+        warn!("Failed to read the solution to {}", exercise.name);
+        return Err(());
+    };
+    if show_diff {
+        // Files the learner hasn't created yet diff as empty
+        let yours_text = if yours.exists() {
+            let Ok(text) = fs::read_to_string(yours) else {
+                warn!("Failed to read your solution to {}", exercise.name);
+                return Err(());
+            };
+            text
+        } else {
+            String::new()
+        };
+        diff::print_unified(
+            &yours.display().to_string(),
+            &yours_text,
+            &reference.display().to_string(),
+            &reference_text,
+        );
+    } else {
+        if exercise.path.is_dir() {
+            println!("{}", style(reference.display()).bold());
+        }
+        println!("{reference_text}");
+    }
+    Ok(())
+}
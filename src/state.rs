@@ -0,0 +1,138 @@
+use crate::exercise::{Exercise, FileStamp, State};
+use crate::util;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const STATE_FILE: &str = ".rustlings/state.json";
+const STATE_BACKUP: &str = ".rustlings/state.json.bak";
+
+/// The persistent progress store, kept in `.rustlings/state.json`.
+/// An exercise only counts as done once it actually passed `verify`,
+/// and only for as long as its source matches the version that passed.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct StateFile {
+    // Exercises that passed `verify`, keyed by exercise name
+    #[serde(default)]
+    done: BTreeMap<String, DoneRecord>,
+    // How many hint levels have been revealed, keyed by exercise name
+    #[serde(default)]
+    hint_levels: BTreeMap<String, usize>,
+}
+
+// A single exercise that passed `verify`
+#[derive(Serialize, Deserialize, Debug)]
+struct DoneRecord {
+    // Seconds since the Unix epoch at which the exercise passed
+    verified_at: u64,
+    // The SHA-256 hash of the source that passed
+    source_hash: String,
+    // The stamps of the files that passed, so that unchanged
+    // files don't have to be hashed again to tell
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    stamps: Vec<FileStamp>,
+    // Whether the record was migrated from a missing `I AM NOT DONE` marker
+    #[serde(default, skip_serializing_if = "util::is_false")]
+    migrated: bool,
+}
+
+// Seconds since the Unix epoch
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl StateFile {
+    /// Load the progress store, creating it on first run by migrating
+    /// every exercise whose `I AM NOT DONE` marker was already removed.
+    pub fn load(exercises: &[Exercise]) -> StateFile {
+        match fs::read_to_string(STATE_FILE) {
+            Ok(contents) => match serde_json::from_str(&contents) {
+                Ok(state) => return state,
+                Err(e) => {
+                    // Keep the progress around, in case it can be recovered by hand
+                    if let Err(e) = fs::rename(STATE_FILE, STATE_BACKUP) {
+                        warn!("Failed to move the corrupt progress file aside: {}", e);
+                        return StateFile::default();
+                    }
+                    warn!(
+                        "The progress file is corrupt, so it was moved to {}",
+                        format!("{STATE_BACKUP}: {e}")
+                    );
+                }
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => {
+                warn!("Failed to read the progress file: {}", e);
+                return StateFile::default();
+            }
+        }
+
+        let mut state = StateFile::default();
+        for exercise in exercises {
+            if exercise.state() == State::Done {
+                state.done.insert(
+                    exercise.name.clone(),
+                    DoneRecord {
+                        verified_at: now(),
+                        source_hash: exercise.source_hash(),
+                        stamps: exercise.source_stamps(),
+                        migrated: true,
+                    },
+                );
+            }
+        }
+        if let Err(e) = state.save() {
+            warn!("Failed to save the progress file: {}", e);
+        }
+        state
+    }
+
+    /// Whether the exercise passed `verify` and hasn't been edited since.
+    /// Files whose stamps haven't changed aren't read again.
+    pub fn is_done(&self, exercise: &Exercise) -> bool {
+        self.done.get(&exercise.name).is_some_and(|record| {
+            (!record.stamps.is_empty() && record.stamps == exercise.source_stamps())
+                || record.source_hash == exercise.source_hash()
+        })
+    }
+
+    /// Record that the exercise passed `verify` and persist the store.
+    pub fn mark_done(&mut self, exercise: &Exercise) -> io::Result<()> {
+        self.done.insert(
+            exercise.name.clone(),
+            DoneRecord {
+                verified_at: now(),
+                source_hash: exercise.source_hash(),
+                stamps: exercise.source_stamps(),
+                migrated: false,
+            },
+        );
+        self.save()
+    }
+
+    /// How many of the exercise's hints have been revealed so far.
+    pub fn hint_level(&self, exercise: &Exercise) -> usize {
+        self.hint_levels.get(&exercise.name).copied().unwrap_or(0)
+    }
+
+    /// Remember that the exercise's hints up to `level` have been revealed.
+    /// Hints are never hidden again, so lower levels are ignored.
+    pub fn reveal_hints(&mut self, exercise: &Exercise, level: usize) -> io::Result<()> {
+        if level <= self.hint_level(exercise) {
+            return Ok(());
+        }
+        self.hint_levels.insert(exercise.name.clone(), level);
+        self.save()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).expect("Failed to serialize to JSON");
+        util::write_atomically(Path::new(STATE_FILE), json)
+    }
+}
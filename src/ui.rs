@@ -1,9 +1,8 @@
 macro_rules! warn {
     ($fmt:literal, $ex:expr) => {{
         use console::{style, Emoji};
-        use std::env;
         let formatstr = format!($fmt, $ex);
-        if env::var("NO_EMOJI").is_ok() {
+        if !crate::config::get().emoji() {
             println!("{} {}", style("!").red(), style(formatstr).red());
         } else {
             println!(
@@ -18,9 +17,8 @@ macro_rules! warn {
 macro_rules! success {
     ($fmt:literal, $ex:expr) => {{
         use console::{style, Emoji};
-        use std::env;
         let formatstr = format!($fmt, $ex);
-        if env::var("NO_EMOJI").is_ok() {
+        if !crate::config::get().emoji() {
             println!("{} {}", style("✓").green(), style(formatstr).green());
         } else {
             println!(
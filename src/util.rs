@@ -0,0 +1,33 @@
+use regex::Regex;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process;
+use std::sync::OnceLock;
+
+/// Write the file atomically, by way of a temporary file next to it,
+/// so that a concurrent reader never observes a half-written file
+pub fn write_atomically(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let dir = path.parent().unwrap_or(Path::new(""));
+    if !dir.as_os_str().is_empty() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}", process::id()));
+    let tmp = dir.join(name);
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, path)
+}
+
+/// Leave flags that aren't set out of serialized files
+pub fn is_false(b: &bool) -> bool {
+    !b
+}
+
+/// The text without the ANSI escape codes that color it, like the ones
+/// in the compiler output
+pub fn strip_colors(text: &str) -> String {
+    static COLORS: OnceLock<Regex> = OnceLock::new();
+    let colors = COLORS.get_or_init(|| Regex::new(r"\x1b\[[0-9;]*m").unwrap());
+    colors.replace_all(text, "").into_owned()
+}
@@ -1,45 +1,98 @@
-use crate::exercise::{CompiledExercise, Exercise, Mode, State};
+use crate::cache;
+use crate::config;
+use crate::diff;
+use crate::exercise::{Exercise, ExerciseOutput, Mode, RunFailure, State};
+use crate::history;
+use crate::limits::Resource;
+use crate::report::{self, Format, VerifyEntry};
+use crate::rustfmt;
+use crate::session;
+use crate::state::StateFile;
 use console::style;
 use indicatif::{ProgressBar, ProgressStyle};
-use std::{env, time::Duration};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// How the exercises of a verify run are checked and reported
+pub struct VerifyOptions {
+    // Whether to display the test harness outputs of test exercises
+    pub verbose: bool,
+    // Whether to display an exercise's hint once it passes
+    pub success_hints: bool,
+    // The number of exercises to check concurrently
+    pub jobs: usize,
+    // Whether to talk to a human, or print a machine-readable report
+    pub format: Format,
+    // Where to write a JUnit XML report to, if anywhere.
+    // Writing one checks every exercise instead of stopping at the first failure
+    pub junit: Option<PathBuf>,
+    // Whether to snapshot every checked exercise into its edit history
+    pub history: bool,
+}
 
 // Verify that the provided container of Exercise objects
 // can be compiled and run without any failures.
 // Any such failures will be reported to the end user.
-// If the Exercise being verified is a test, the verbose boolean
-// determines whether or not the test harness outputs are displayed.
+// Every exercise that passes is recorded in the progress store.
+// The first exercise that isn't done yet is returned as the error.
+// With more than one job, the exercises are checked concurrently by a pool
+// of worker threads, but still reported in order up to the first failure.
 pub fn verify<'a>(
     exercises: impl IntoIterator<Item = &'a Exercise>,
     progress: (usize, usize),
-    verbose: bool,
-    success_hints: bool,
+    options: &VerifyOptions,
+    state: &mut StateFile,
 ) -> Result<(), &'a Exercise> {
-    let (num_done, total) = progress;
-    let bar = ProgressBar::new(total as u64);
-    let mut percentage = num_done as f32 / total as f32 * 100.0;
-    bar.set_style(
-        ProgressStyle::default_bar()
-            .template("Progress: [{bar:60.green/red}] {pos}/{len} {msg}")
-            .expect("Progressbar template should be valid!")
-            .progress_chars("#>-"),
-    );
-    bar.set_position(num_done as u64);
-    bar.set_message(format!("({:.1} %)", percentage));
-
-    for exercise in exercises {
-        let compile_result = match exercise.mode {
-            Mode::Test => compile_and_test(exercise, RunMode::Interactive, verbose, success_hints),
-            Mode::Compile => compile_and_run_interactively(exercise, success_hints),
-            Mode::Clippy => compile_only(exercise, success_hints),
-        };
-        if !compile_result.unwrap_or(false) {
-            return Err(exercise);
-        }
-        percentage += 100.0 / total as f32;
-        bar.inc(1);
-        bar.set_message(format!("({:.1} %)", percentage));
+    let exercises: Vec<&'a Exercise> = exercises.into_iter().collect();
+    let reporter = Reporter { progress, options };
+    let jobs = options.jobs.min(exercises.len());
+    if jobs <= 1 {
+        return reporter.report_in_order(&exercises, |index| check(exercises[index]), state);
     }
-    Ok(())
+
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    thread::scope(|scope| {
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..jobs {
+            let tx = tx.clone();
+            let (exercises, next, stop) = (&exercises, &next, &stop);
+            scope.spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(exercise) = exercises.get(index) else {
+                        break;
+                    };
+                    if tx.send((index, check(exercise))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        // Outcomes arrive in whatever order the workers finish them
+        let mut received = HashMap::new();
+        let result = reporter.report_in_order(
+            &exercises,
+            |index| loop {
+                if let Some(checked) = received.remove(&index) {
+                    return checked;
+                }
+                let (i, checked) = rx.recv().expect("A verify worker thread panicked");
+                received.insert(i, checked);
+            },
+            state,
+        );
+        // Let the workers finish what they're checking, but not start anything new
+        stop.store(true, Ordering::Relaxed);
+        result
+    })
 }
 
 enum RunMode {
@@ -47,113 +100,448 @@ enum RunMode {
     NonInteractive,
 }
 
-// Compile and run the resulting test harness of the given Exercise
-pub fn test(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
-    compile_and_test(exercise, RunMode::NonInteractive, verbose, false)?;
-    Ok(())
+// The result of compiling and running an exercise, before it is reported
+#[derive(Serialize, Deserialize, Clone)]
+pub enum Outcome {
+    // The exercise failed to compile
+    CompileFailed(ExerciseOutput),
+    // The binary or the test harness exited with an error
+    RunFailed(ExerciseOutput),
+    // The binary or the test harness ran out of time and was killed
+    TimedOut(ExerciseOutput),
+    // The binary or the test harness used up its share of a resource
+    LimitExceeded(Resource, ExerciseOutput),
+    // The binary ran successfully, but didn't print the expected output
+    WrongOutput(ExerciseOutput),
+    // A compile_fail exercise compiled
+    CompiledUnexpectedly,
+    // A compile_fail exercise failed to compile, but with other errors
+    WrongError(ExerciseOutput),
+    // The exercise passed, but isn't formatted as rustfmt would format it.
+    // The output holds the diff rustfmt suggests
+    Unformatted(ExerciseOutput),
+    // The exercise compiled and ran successfully.
+    // Clippy exercises are never run, so they have no output
+    Passed(Option<ExerciseOutput>),
 }
 
-// Invoke the rust compiler without running the resulting binary
-fn compile_only(exercise: &Exercise, success_hints: bool) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
-    progress_bar.enable_steady_tick(Duration::from_millis(100));
+impl Outcome {
+    // How the outcome is called in reports and in the edit history
+    pub fn name(&self) -> &'static str {
+        match self {
+            Outcome::CompileFailed(_) => "compile_failed",
+            Outcome::RunFailed(_) => "run_failed",
+            Outcome::TimedOut(_) => "timed_out",
+            Outcome::LimitExceeded(Resource::Memory, _) => "memory_exceeded",
+            Outcome::LimitExceeded(Resource::CpuTime, _) => "cpu_time_exceeded",
+            Outcome::WrongOutput(_) => "wrong_output",
+            Outcome::CompiledUnexpectedly => "compiled_unexpectedly",
+            Outcome::WrongError(_) => "wrong_error",
+            Outcome::Unformatted(_) => "unformatted",
+            Outcome::Passed(_) => "passed",
+        }
+    }
+}
 
-    let _ = compile(exercise, &progress_bar)?;
-    progress_bar.finish_and_clear();
+// Check the given Exercise, unless it hasn't changed since its last check,
+// and measure how long that took
+fn check(exercise: &Exercise) -> (Outcome, Duration) {
+    let start = Instant::now();
+    if let Some(outcome) = cache::lookup(exercise) {
+        return (outcome, start.elapsed());
+    }
+    let outcome = check_formatting(exercise, compile_and_run(exercise));
+    // A busy machine may time out what would otherwise pass
+    if !matches!(outcome, Outcome::TimedOut(_)) {
+        cache::store(exercise, &outcome);
+    }
+    (outcome, start.elapsed())
+}
+
+// Compile the given Exercise and, unless it's a Clippy exercise,
+// run the resulting binary or test harness.
+// A compile_fail exercise passes once it fails with expected errors only
+fn compile_and_run(exercise: &Exercise) -> Outcome {
+    if let Mode::CompileFail = exercise.mode {
+        return match exercise.compile() {
+            Ok(_) => Outcome::CompiledUnexpectedly,
+            Err(output) => {
+                let codes = Exercise::error_codes(&output);
+                if !codes.is_empty()
+                    && codes
+                        .iter()
+                        .all(|code| exercise.expected_errors.contains(code))
+                {
+                    Outcome::Passed(Some(output))
+                } else {
+                    Outcome::WrongError(output)
+                }
+            }
+        };
+    }
+    let compilation = match exercise.compile() {
+        Ok(compilation) => compilation,
+        Err(output) => return Outcome::CompileFailed(output),
+    };
+    match exercise.mode {
+        Mode::Clippy | Mode::CompileFail => Outcome::Passed(None),
+        Mode::Compile | Mode::Test | Mode::Cargo => match compilation.run() {
+            Ok(output) => Outcome::Passed(Some(output)),
+            Err(RunFailure::Failed(output)) => Outcome::RunFailed(output),
+            Err(RunFailure::TimedOut(output)) => Outcome::TimedOut(output),
+            Err(RunFailure::LimitExceeded(resource, output)) => {
+                Outcome::LimitExceeded(resource, output)
+            }
+            Err(RunFailure::WrongOutput(output)) => Outcome::WrongOutput(output),
+        },
+    }
+}
 
-    Ok(prompt_for_completion(exercise, None, success_hints))
+// Exercises that require formatting only pass once rustfmt has nothing to change
+pub fn check_formatting(exercise: &Exercise, outcome: Outcome) -> Outcome {
+    match outcome {
+        Outcome::Passed(_) if exercise.require_fmt => match rustfmt::check(exercise) {
+            Ok(()) => outcome,
+            Err(diff) => Outcome::Unformatted(diff),
+        },
+        outcome => outcome,
+    }
 }
 
-// Compile the given Exercise and run the resulting binary in an interactive mode
-fn compile_and_run_interactively(exercise: &Exercise, success_hints: bool) -> Result<bool, ()> {
+// Compile and run the resulting test harness of the given Exercise,
+// or check that a compile_fail exercise fails as it should
+pub fn test(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
     let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
+    let action = match exercise.mode {
+        Mode::CompileFail => "Compiling",
+        _ => "Testing",
+    };
+    progress_bar.set_message(format!("{action} {exercise}..."));
     progress_bar.enable_steady_tick(Duration::from_millis(100));
+    let (outcome, _) = check(exercise);
+    progress_bar.finish_and_clear();
 
-    let compilation = compile(exercise, &progress_bar)?;
+    if report(exercise, &outcome, RunMode::NonInteractive, verbose, false) {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
 
-    progress_bar.set_message(format!("Running {exercise}..."));
-    let result = compilation.run();
-    progress_bar.finish_and_clear();
+// Displays the outcomes of a verify run on a single progress bar,
+// or collects them into a machine-readable report
+struct Reporter<'o> {
+    progress: (usize, usize),
+    options: &'o VerifyOptions,
+}
 
-    let output = match result {
-        Ok(output) => output,
-        Err(output) => {
-            warn!("Ran {} with errors", exercise);
-            println!("{}", output.stdout);
-            println!("{}", output.stderr);
-            return Err(());
+impl Reporter<'_> {
+    // Report the outcome of each exercise in order, fetching them with
+    // `outcome_of`, until the first one that isn't done yet
+    fn report_in_order<'a>(
+        &self,
+        exercises: &[&'a Exercise],
+        mut outcome_of: impl FnMut(usize) -> (Outcome, Duration),
+        state: &mut StateFile,
+    ) -> Result<(), &'a Exercise> {
+        let human = self.options.format == Format::Human;
+        let mut checked = Vec::new();
+        let result = self.report_each(
+            exercises,
+            |index| {
+                let (outcome, duration) = outcome_of(index);
+                let exercise = exercises[index];
+                if self.options.history {
+                    history::record(exercise, &outcome);
+                }
+                // A report is meant for CI, which can't remove any comments
+                let interactive = human && self.options.junit.is_none();
+                let done = if interactive {
+                    let done = report(
+                        exercise,
+                        &outcome,
+                        RunMode::Interactive,
+                        self.options.verbose,
+                        self.options.success_hints,
+                    );
+                    if !done {
+                        print_exercise_info(exercise);
+                    }
+                    done
+                } else {
+                    if human {
+                        report(
+                            exercise,
+                            &outcome,
+                            RunMode::NonInteractive,
+                            self.options.verbose,
+                            false,
+                        );
+                    }
+                    matches!(outcome, Outcome::Passed(_)) && exercise.state() == State::Done
+                };
+                checked.push((outcome, duration, done));
+                done
+            },
+            state,
+        );
+
+        let rows: Vec<VerifyEntry> = exercises
+            .iter()
+            .zip(&checked)
+            .map(|(exercise, (outcome, duration, done))| {
+                VerifyEntry::new(exercise, outcome, *duration, *done)
+            })
+            .collect();
+        if !human {
+            report::write_rows(self.options.format, &rows);
         }
-    };
+        if let Some(path) = &self.options.junit {
+            if let Err(e) = report::write_junit(path, &rows) {
+                warn!("Failed to write the JUnit report: {}", e);
+                session::exit(1);
+            }
+        }
+        result
+    }
+
+    // Walk through the exercises in order on the progress bar,
+    // until `is_done` says one of them isn't done yet.
+    // When writing a JUnit report, every exercise is walked through
+    fn report_each<'a>(
+        &self,
+        exercises: &[&'a Exercise],
+        mut is_done: impl FnMut(usize) -> bool,
+        state: &mut StateFile,
+    ) -> Result<(), &'a Exercise> {
+        let human = self.options.format == Format::Human;
+        let (num_done, total) = self.progress;
+        let bar = if human {
+            ProgressBar::new(total as u64)
+        } else {
+            ProgressBar::hidden()
+        };
+        let mut percentage = num_done as f32 / total as f32 * 100.0;
+        bar.set_style(
+            ProgressStyle::default_bar()
+                .template("Progress: [{bar:60.green/red}] {pos}/{len} {msg}")
+                .expect("Progressbar template should be valid!")
+                .progress_chars("#>-"),
+        );
+        bar.set_position(num_done as u64);
+        bar.set_message(format!("({:.1} %)", percentage));
+
+        let mut first_failure = None;
+        for (index, &exercise) in exercises.iter().enumerate() {
+            let progress_bar = if human {
+                ProgressBar::new_spinner()
+            } else {
+                ProgressBar::hidden()
+            };
+            let action = match exercise.mode {
+                Mode::Test | Mode::Cargo => "Testing",
+                Mode::Compile | Mode::Clippy | Mode::CompileFail => "Compiling",
+            };
+            progress_bar.set_message(format!("{action} {exercise}..."));
+            progress_bar.enable_steady_tick(Duration::from_millis(100));
+            let done = is_done(index);
+            progress_bar.finish_and_clear();
 
-    Ok(prompt_for_completion(
-        exercise,
-        Some(output.stdout),
-        success_hints,
-    ))
+            if !done {
+                if self.options.junit.is_none() {
+                    return Err(exercise);
+                }
+                first_failure = first_failure.or(Some(exercise));
+                continue;
+            }
+            if let Err(e) = state.mark_done(exercise) {
+                warn!("Failed to save your progress: {}", e);
+            }
+            percentage += 100.0 / total as f32;
+            bar.inc(1);
+            bar.set_message(format!("({:.1} %)", percentage));
+        }
+        first_failure.map_or(Ok(()), Err)
+    }
 }
 
-// Compile the given Exercise as a test harness and display
-// the output if verbose is set to true
-fn compile_and_test(
+// Display the outcome of checking the given Exercise,
+// and return whether the exercise is done
+fn report(
     exercise: &Exercise,
+    outcome: &Outcome,
     run_mode: RunMode,
     verbose: bool,
     success_hints: bool,
-) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Testing {exercise}..."));
-    progress_bar.enable_steady_tick(Duration::from_millis(100));
-
-    let compilation = compile(exercise, &progress_bar)?;
-    let result = compilation.run();
-    progress_bar.finish_and_clear();
-
-    match result {
-        Ok(output) => {
-            if verbose {
-                println!("{}", output.stdout);
-            }
-            if let RunMode::Interactive = run_mode {
-                Ok(prompt_for_completion(exercise, None, success_hints))
-            } else {
-                Ok(true)
-            }
+) -> bool {
+    match (outcome, exercise.mode) {
+        (Outcome::CompileFailed(output), _) => {
+            warn!(
+                "Compiling of {} failed! Please try again. Here's the output:",
+                exercise
+            );
+            println!("{}", output.stderr);
+            false
         }
-        Err(output) => {
+        (Outcome::RunFailed(output), Mode::Test | Mode::Cargo) => {
             warn!(
                 "Testing of {} failed! Please try again. Here's the output:",
                 exercise
             );
             println!("{}", output.stdout);
-            Err(())
+            false
         }
-    }
-}
-
-// Compile the given Exercise and return an object with information
-// about the state of the compilation
-fn compile<'a>(
-    exercise: &'a Exercise,
-    progress_bar: &ProgressBar,
-) -> Result<CompiledExercise<'a>, ()> {
-    let compilation_result = exercise.compile();
-
-    match compilation_result {
-        Ok(compilation) => Ok(compilation),
-        Err(output) => {
-            progress_bar.finish_and_clear();
+        (Outcome::RunFailed(output), _) => {
+            warn!("Ran {} with errors", exercise);
+            println!("{}", output.stdout);
+            println!("{}", output.stderr);
+            false
+        }
+        (Outcome::TimedOut(output), _) => {
+            print_timed_out(exercise, output);
+            false
+        }
+        (Outcome::LimitExceeded(resource, output), _) => {
+            print_limit_exceeded(exercise, *resource, output);
+            false
+        }
+        (Outcome::WrongOutput(output), _) => {
+            print_wrong_output(exercise, output);
+            false
+        }
+        (Outcome::CompiledUnexpectedly, _) => {
             warn!(
-                "Compiling of {} failed! Please try again. Here's the output:",
+                "{} compiled, but it should fail with one of these errors",
                 exercise
             );
+            println!("{}", exercise.expected_errors.join(", "));
+            false
+        }
+        (Outcome::WrongError(output), _) => {
+            warn!(
+                "Compiling of {}, but not with the expected error. Here's the output:",
+                format!(
+                    "{exercise} failed with {}",
+                    Exercise::error_codes(output).join(", ")
+                )
+            );
             println!("{}", output.stderr);
-            Err(())
+            println!(
+                "It should fail with these errors only: {}",
+                exercise.expected_errors.join(", ")
+            );
+            false
+        }
+        (Outcome::Unformatted(output), _) => {
+            print_unformatted(exercise, output);
+            false
+        }
+        (Outcome::Passed(output), Mode::Test | Mode::Cargo) => {
+            if verbose {
+                if let Some(output) = output {
+                    println!("{}", output.stdout);
+                }
+            }
+            match run_mode {
+                RunMode::Interactive => prompt_for_completion(exercise, None, success_hints),
+                RunMode::NonInteractive => true,
+            }
+        }
+        (Outcome::Passed(output), Mode::Compile) => match run_mode {
+            RunMode::Interactive => prompt_for_completion(
+                exercise,
+                output.as_ref().map(|o| o.stdout.clone()),
+                success_hints,
+            ),
+            RunMode::NonInteractive => true,
+        },
+        (Outcome::Passed(_), Mode::Clippy) => match run_mode {
+            RunMode::Interactive => prompt_for_completion(exercise, None, success_hints),
+            RunMode::NonInteractive => true,
+        },
+        (Outcome::Passed(output), Mode::CompileFail) => {
+            if verbose {
+                if let Some(output) = output {
+                    println!("{}", output.stderr);
+                }
+            }
+            match run_mode {
+                RunMode::Interactive => prompt_for_completion(exercise, None, success_hints),
+                RunMode::NonInteractive => true,
+            }
         }
     }
 }
 
+// Tell the learner an exercise works but isn't formatted,
+// along with the changes rustfmt would make
+pub fn print_unformatted(exercise: &Exercise, output: &ExerciseOutput) {
+    warn!(
+        "{} works, but it isn't formatted the way rustfmt would format it. Here's the difference:",
+        exercise
+    );
+    diff::print_colored(&output.stdout);
+    println!("{}", output.stderr);
+    println!(
+        "Run `rustlings fmt {}` to apply the formatting",
+        exercise.name
+    );
+}
+
+// Tell the learner an exercise was killed for running too long,
+// along with whatever it printed until then
+pub fn print_timed_out(exercise: &Exercise, output: &ExerciseOutput) {
+    warn!(
+        "{}! Is there an infinite loop or a deadlock? Here's the output:",
+        format!(
+            "{exercise} timed out after {} seconds",
+            exercise.limits().timeout.as_secs()
+        )
+    );
+    println!("{}", output.stdout);
+    println!("{}", output.stderr);
+}
+
+// Tell the learner an exercise was stopped for using too much of a resource,
+// which is most likely an endless loop that keeps allocating or computing
+pub fn print_limit_exceeded(exercise: &Exercise, resource: Resource, output: &ExerciseOutput) {
+    let limits = exercise.limits();
+    let limit = match resource {
+        Resource::Memory => limits.memory.map(|memory| format!("{memory} MiB")),
+        Resource::CpuTime => Some(format!("{} seconds", limits.cpu_time)),
+    };
+    let message = match limit {
+        Some(limit) => format!("{exercise} exceeded its {resource} limit of {limit}"),
+        None => format!("{exercise} ran out of {resource}"),
+    };
+    warn!(
+        "{}! Does it allocate or compute without end? Here's the output:",
+        message
+    );
+    println!("{}", output.stdout);
+    println!("{}", output.stderr);
+}
+
+// Show the learner how the output of an exercise differs from the expected one
+pub fn print_wrong_output(exercise: &Exercise, output: &ExerciseOutput) {
+    warn!(
+        "Ran {} without errors, but it didn't print what it should. Here's the difference:",
+        exercise
+    );
+    let expected = exercise
+        .expected_output()
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    diff::print_unified(
+        "expected output",
+        &exercise.normalize_output(&expected),
+        "your output",
+        &exercise.normalize_output(&output.stdout),
+    );
+    println!("{}", output.stderr);
+}
+
 fn prompt_for_completion(
     exercise: &Exercise,
     prompt_output: Option<String>,
@@ -165,11 +553,12 @@ fn prompt_for_completion(
     };
     match exercise.mode {
         Mode::Compile => success!("Successfully ran {}!", exercise),
-        Mode::Test => success!("Successfully tested {}!", exercise),
+        Mode::Test | Mode::Cargo => success!("Successfully tested {}!", exercise),
         Mode::Clippy => success!("Successfully compiled {}!", exercise),
+        Mode::CompileFail => success!("{} fails to compile as expected!", exercise),
     }
 
-    let no_emoji = env::var("NO_EMOJI").is_ok();
+    let no_emoji = !config::get().emoji();
 
     let clippy_success_msg = if no_emoji {
         "The code is compiling, and Clippy is happy!"
@@ -179,8 +568,9 @@ fn prompt_for_completion(
 
     let success_msg = match exercise.mode {
         Mode::Compile => "The code is compiling!",
-        Mode::Test => "The code is compiling, and the tests pass!",
+        Mode::Test | Mode::Cargo => "The code is compiling, and the tests pass!",
         Mode::Clippy => clippy_success_msg,
+        Mode::CompileFail => "The code fails with the expected error!",
     };
     println!();
     if no_emoji {
@@ -200,7 +590,7 @@ fn prompt_for_completion(
     if success_hints {
         println!("Hints:");
         println!("{}", separator());
-        println!("{}", exercise.hint);
+        println!("{}", exercise.hints.join("\n"));
         println!("{}", separator());
         println!();
     }
@@ -229,6 +619,27 @@ fn prompt_for_completion(
     false
 }
 
+// Tell the learner what the exercise they're working on is about
+fn print_exercise_info(exercise: &Exercise) {
+    let mut info = Vec::new();
+    if let Some(difficulty) = exercise.difficulty {
+        info.push(format!("Difficulty: {difficulty}"));
+    }
+    if let Some(chapter) = &exercise.book_chapter {
+        info.push(format!("Book chapter: {chapter}"));
+    }
+    if !exercise.topics.is_empty() {
+        info.push(format!("Topics: {}", exercise.topics.join(", ")));
+    }
+    if !exercise.tags.is_empty() {
+        info.push(format!("Tags: {}", exercise.tags.join(", ")));
+    }
+    if !info.is_empty() {
+        println!();
+        println!("{}", style(info.join(" | ")).dim());
+    }
+}
+
 fn separator() -> console::StyledObject<&'static str> {
     style("====================").bold()
 }
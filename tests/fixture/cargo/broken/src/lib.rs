@@ -0,0 +1,11 @@
+mod double;
+
+#[cfg(test)]
+mod tests {
+    use super::double::double;
+
+    #[test]
+    fn doubles() {
+        assert_eq!(double(2), 4);
+    }
+}
@@ -0,0 +1,14 @@
+mod square;
+
+pub use square::Square;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn area_of_square() {
+        println!("THE SQUARE IS MEASURED");
+        assert_eq!(Square(3).area(), 9);
+    }
+}
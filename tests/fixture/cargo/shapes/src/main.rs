@@ -0,0 +1,5 @@
+use shapes::Square;
+
+fn main() {
+    println!("{}", Square(2).area());
+}
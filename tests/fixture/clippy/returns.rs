@@ -0,0 +1,7 @@
+fn answer() -> u32 {
+    return 42;
+}
+
+fn main() {
+    println!("{}", answer());
+}
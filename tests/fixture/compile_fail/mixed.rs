@@ -0,0 +1,10 @@
+fn main() {
+    let s = String::from("moved");
+    let t = s;
+    println!("{s} {t}");
+
+    let mut n = 1;
+    let a = &mut n;
+    let b = &mut n;
+    *a += *b;
+}
@@ -0,0 +1,6 @@
+#[cfg(not(rustlings))]
+compile_error!("rustlings isn't set");
+
+fn main() {
+    println!("rustlings is set");
+}
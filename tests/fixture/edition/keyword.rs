@@ -0,0 +1,5 @@
+fn main() {
+    // `async` only became a keyword in the 2018 edition
+    let async = 2015;
+    println!("{}", async);
+}
@@ -0,0 +1,7 @@
+#[test]
+fn keeps_allocating() {
+    let mut chunks = Vec::new();
+    loop {
+        chunks.push(vec![1u8; 1 << 20]);
+    }
+}
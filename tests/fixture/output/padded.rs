@@ -0,0 +1,4 @@
+fn main() {
+    println!("first   ");
+    println!("second\n");
+}
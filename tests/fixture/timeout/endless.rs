@@ -0,0 +1,6 @@
+fn main() {
+    println!("Looping forever");
+    loop {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+}
@@ -1,10 +1,42 @@
 use assert_cmd::prelude::*;
 use glob::glob;
 use predicates::boolean::PredicateBooleanExt;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+// Copy a fixture into a fresh temporary directory, for tests that
+// depend on the progress rustlings remembers between runs
+fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        if entry.file_name() == ".rustlings" {
+            continue;
+        }
+        if entry.file_type().unwrap().is_dir() {
+            copy_dir(&entry.path(), &to.join(entry.file_name()));
+        } else {
+            fs::copy(entry.path(), to.join(entry.file_name())).unwrap();
+        }
+    }
+}
+
+fn fixture_copy(name: &str) -> PathBuf {
+    let thread_id: String = format!("{:?}", std::thread::current().id())
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect();
+    let dir = std::env::temp_dir().join(format!(
+        "rustlings-test-{name}-{}-{thread_id}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    copy_dir(&Path::new("tests/fixture").join(name), &dir);
+    dir
+}
+
 #[test]
 fn runs_without_arguments() {
     let mut cmd = Command::cargo_bin("rustlings").unwrap();
@@ -30,6 +62,59 @@ fn verify_all_success() {
         .success();
 }
 
+#[test]
+fn verify_records_progress() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir("tests/fixture/success")
+        .assert()
+        .success();
+    let state = fs::read_to_string("tests/fixture/success/.rustlings/state.json").unwrap();
+    assert!(state.contains("\"compSuccess\""));
+    assert!(state.contains("\"testSuccess\""));
+    assert!(state.contains("\"stamps\""));
+}
+
+#[test]
+fn corrupt_progress_is_moved_aside() {
+    let dir = fixture_copy("success");
+    fs::create_dir_all(dir.join(".rustlings")).unwrap();
+    fs::write(dir.join(".rustlings/state.json"), "{\"done\": {").unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("list")
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("progress file is corrupt"));
+    let backup = fs::read_to_string(dir.join(".rustlings/state.json.bak")).unwrap();
+    assert_eq!(backup, "{\"done\": {");
+}
+
+#[test]
+fn verify_reports_cached_failures() {
+    for _ in 0..2 {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .arg("verify")
+            .current_dir("tests/fixture/failure")
+            .assert()
+            .code(1)
+            .stdout(predicates::str::contains(
+                "Compiling of compFailure.rs failed!",
+            ));
+    }
+    let cache = fs::read_to_string("tests/fixture/failure/.rustlings/cache.json").unwrap();
+    assert!(cache.contains("\"compFailure\""));
+    assert!(cache.contains(concat!(
+        "\"rustlings_version\":\"",
+        env!("CARGO_PKG_VERSION"),
+        "\""
+    )));
+}
+
 #[test]
 fn verify_fails_if_some_fails() {
     Command::cargo_bin("rustlings")
@@ -40,6 +125,30 @@ fn verify_fails_if_some_fails() {
         .code(1);
 }
 
+#[test]
+fn verify_all_success_in_parallel() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--jobs", "4"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .success();
+}
+
+#[test]
+fn verify_in_parallel_stops_at_first_failure() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--jobs", "4"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("compFailure.rs")
+                .and(predicates::str::contains("testFailure.rs").not()),
+        );
+}
+
 #[test]
 fn run_single_compile_success() {
     Command::cargo_bin("rustlings")
@@ -267,3 +376,1082 @@ fn run_rustlings_list_without_done() {
         .success()
         .stdout(predicates::str::contains("Done").not());
 }
+
+#[test]
+fn run_rustlings_list_as_json() {
+    let output = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--format", "json"])
+        .current_dir("tests/fixture/state")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let list: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let list = list.as_array().unwrap();
+    assert_eq!(list.len(), 3);
+    assert_eq!(list[0]["name"], "pending_exercise");
+    assert_eq!(list[0]["mode"], "compile");
+    assert_eq!(list[0]["status"], "pending");
+    assert_eq!(list[2]["status"], "done");
+}
+
+#[test]
+fn run_rustlings_list_as_csv() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--format", "csv"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::starts_with("name,path,mode,status,category,")
+                .and(predicates::str::contains("Progress").not()),
+        );
+}
+
+#[test]
+fn verify_as_json() {
+    let output = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--format", "json"])
+        .current_dir("tests/fixture/failure")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report[0]["name"], "compFailure");
+    assert_eq!(report[0]["outcome"], "compile_failed");
+    assert_eq!(report[0]["done"], false);
+    assert!(report[0]["stderr"]
+        .as_str()
+        .unwrap()
+        .contains("error: expected pattern"));
+}
+
+#[test]
+fn verify_junit_reports_every_exercise() {
+    let report = std::env::temp_dir().join(format!("rustlings-junit-{}.xml", std::process::id()));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .arg("--junit")
+        .arg(&report)
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(1);
+    let xml = fs::read_to_string(&report).unwrap();
+    fs::remove_file(&report).unwrap();
+    assert!(xml.contains(r#"tests="2" failures="2""#));
+    assert!(xml.contains(r#"<testcase name="compFailure""#));
+    assert!(xml.contains(r#"<testcase name="testFailure""#));
+    assert!(xml.contains(r#"<failure type="compile_failed""#));
+}
+
+#[test]
+fn verify_junit_doesnt_prompt_for_completion() {
+    let dir = fixture_copy("state");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--junit", "report.xml"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("You can keep working").not());
+    let xml = fs::read_to_string(dir.join("report.xml")).unwrap();
+    assert!(xml.contains(r#"<testcase name="pending_exercise""#));
+}
+
+#[test]
+fn show_solution_of_solved_exercise() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["solution", "compSuccess"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("The reference solution"));
+}
+
+#[test]
+fn show_solution_diff() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["solution", "compSuccess", "--diff"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("+++ solutions/compSuccess.rs").and(
+                predicates::str::contains("+    println!(\"The reference solution\");"),
+            ),
+        );
+}
+
+#[test]
+fn show_solution_of_cargo_exercise() {
+    let dir = fixture_copy("cargo");
+    fs::create_dir_all(dir.join("solutions/shapes/src")).unwrap();
+    fs::write(
+        dir.join("solutions/shapes/src/square.rs"),
+        "pub struct Square(pub u32); // The reference square\n",
+    )
+    .unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir(&dir)
+        .assert()
+        .code(1);
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["solution", "shapes"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("// The reference square"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["solution", "shapes", "--diff"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("--- shapes/src/square.rs").and(
+            predicates::str::contains("+++ solutions/shapes/src/square.rs"),
+        ));
+}
+
+#[test]
+fn solution_is_locked_until_solved() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["solution", "pending_exercise"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("The reference solution").not());
+}
+
+#[test]
+fn hints_are_revealed_one_level_at_a_time() {
+    let dir = fixture_copy("state");
+    let hint = |args: &[&str]| {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .arg("hint")
+            .args(args)
+            .current_dir(&dir)
+            .assert()
+            .success()
+    };
+
+    hint(&["pending_exercise"]).stdout(
+        predicates::str::contains("Hint 1/2:\nFirst hint")
+            .and(predicates::str::contains("Second hint").not()),
+    );
+    hint(&["pending_exercise", "--next"]).stdout(
+        predicates::str::contains("Hint 2/2:\nSecond hint")
+            .and(predicates::str::contains("First hint").not()),
+    );
+    hint(&["pending_exercise"]).stdout(
+        predicates::str::contains("First hint").and(predicates::str::contains("Second hint")),
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn get_hint_by_level() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["hint", "pending_exercise", "--level", "2"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("Second hint")
+                .and(predicates::str::contains("First hint").not()),
+        );
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["hint", "pending_exercise", "--level", "3"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn run_rustlings_list_by_tag_and_difficulty() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--names", "--tag", "practice"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("pending_test_exercise")
+                .and(predicates::str::contains("finished_exercise").not()),
+        );
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--names", "--difficulty", "easy"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("_exercise").not());
+}
+
+#[test]
+fn run_rustlings_list_shows_metadata() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::is_match(
+                r"pending_test_exercise.*\thard\s*\t11\.1\s*\tquiz, practice",
+            )
+            .unwrap(),
+        );
+}
+
+#[test]
+fn exercises_are_loaded_from_packs_in_order() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--names", "--pack", "extra.toml"])
+        .current_dir("tests/fixture/packs")
+        .assert()
+        .success()
+        .stdout(predicates::str::starts_with("main\nfrom_dir\nextra\n"));
+}
+
+#[test]
+fn duplicate_exercise_names_in_packs_fail() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--pack", "duplicate.toml"])
+        .current_dir("tests/fixture/packs")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "The exercise `from_dir` in duplicate.toml is already defined in info.d/10-dir.toml",
+        ));
+}
+
+#[test]
+fn new_exercise_is_created_and_registered() {
+    let dir = fixture_copy("state");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["new", "basics1", "--category", "basics", "--mode", "test"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    let source = fs::read_to_string(dir.join("exercises/basics/basics1.rs")).unwrap();
+    assert!(source.contains("I AM NOT DONE"));
+    assert!(source.contains("#[cfg(test)]"));
+    // The stub compiles without any warnings
+    Command::new("rustc")
+        .args(["--test", "-D", "warnings", "exercises/basics/basics1.rs"])
+        .arg("-o")
+        .arg(dir.join("basics1"))
+        .current_dir(&dir)
+        .assert()
+        .success();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--names"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("finished_exercise\nbasics1\n"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["new", "basics1", "--category", "basics"])
+        .current_dir(&dir)
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn check_manifest_reports_every_problem() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["check-manifest", "--pack", "broken.toml"])
+        .current_dir("tests/fixture/manifest")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "info.toml:8: The path exercises/missing.rs doesn't exist",
+        ))
+        .stdout(predicates::str::contains(
+            "info.toml:14: exercises/untested.rs is a test exercise without any `#[test]`",
+        ))
+        .stdout(predicates::str::contains(
+            "info.toml:14: exercises/untested.rs has no `I AM NOT DONE` marker",
+        ))
+        .stdout(predicates::str::contains(
+            "info.toml:20: `good` is already defined at info.toml:2",
+        ))
+        .stdout(predicates::str::contains("broken.toml:3: Invalid manifest"))
+        .stdout(predicates::str::contains(
+            "exercises/orphan.rs: Not registered in any manifest",
+        ));
+}
+
+#[test]
+fn check_manifest_accepts_valid_manifest() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("check-manifest")
+        .current_dir("tests/fixture/packs")
+        .assert()
+        .success();
+}
+
+#[test]
+fn reset_restores_original_and_backs_up_changes() {
+    let dir = fixture_copy("state");
+    let original = fs::read_to_string(dir.join("pending_exercise.rs")).unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--names"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    fs::write(dir.join("pending_exercise.rs"), "fn main() {}\n").unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["reset", "pending_exercise"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Your version was saved to"));
+    assert_eq!(
+        fs::read_to_string(dir.join("pending_exercise.rs")).unwrap(),
+        original
+    );
+    let backups: Vec<_> = fs::read_dir(dir.join(".rustlings/backup"))
+        .unwrap()
+        .map(|entry| fs::read_to_string(entry.unwrap().path()).unwrap())
+        .collect();
+    assert_eq!(backups, ["fn main() {}\n"]);
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["reset", "--all"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn reset_restores_built_in_original_of_edited_exercise() {
+    // A checkout whose exercise was already edited before rustlings first ran
+    let dir = fixture_copy("success").join("checkout");
+    copy_dir(Path::new("exercises"), &dir.join("exercises"));
+    fs::copy("info.toml", dir.join("info.toml")).unwrap();
+    let original = fs::read_to_string("exercises/intro/intro1.rs").unwrap();
+    fs::write(dir.join("exercises/intro/intro1.rs"), "fn main() {}\n").unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["reset", "intro1"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    assert_eq!(
+        fs::read_to_string(dir.join("exercises/intro/intro1.rs")).unwrap(),
+        original
+    );
+}
+
+#[test]
+fn reset_restores_built_in_original_after_new_exercise() {
+    // A checkout whose exercise was edited, then got an exercise of its own
+    let dir = fixture_copy("success").join("checkout");
+    copy_dir(Path::new("exercises"), &dir.join("exercises"));
+    fs::copy("info.toml", dir.join("info.toml")).unwrap();
+    let original = fs::read_to_string("exercises/intro/intro1.rs").unwrap();
+    fs::write(dir.join("exercises/intro/intro1.rs"), "fn main() {}\n").unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["new", "intro3", "--category", "intro"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    // The next run sees the rewritten info.toml
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("list")
+        .current_dir(&dir)
+        .assert()
+        .success();
+    assert!(!dir.join(".rustlings/pristine/intro1.rs").exists());
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["reset", "intro1"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    assert_eq!(
+        fs::read_to_string(dir.join("exercises/intro/intro1.rs")).unwrap(),
+        original
+    );
+}
+
+#[test]
+fn restore_snapshot_from_history() {
+    let dir = fixture_copy("state");
+    let history = dir.join(".rustlings/history/pending_exercise");
+    fs::create_dir_all(&history).unwrap();
+    fs::write(history.join("1.rs"), "fn main() {}\n").unwrap();
+    fs::write(
+        history.join("index.json"),
+        r#"[{"id": 1, "taken_at": 0, "source_hash": "", "outcome": "compile_failed"}]"#,
+    )
+    .unwrap();
+    let edited = fs::read_to_string(dir.join("pending_exercise.rs")).unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["history", "pending_exercise"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("days ago"))
+        .stdout(predicates::str::contains("compile_failed"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["restore", "pending_exercise", "1"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("saved as snapshot 2"));
+    assert_eq!(
+        fs::read_to_string(dir.join("pending_exercise.rs")).unwrap(),
+        "fn main() {}\n"
+    );
+    assert_eq!(fs::read_to_string(history.join("2.rs")).unwrap(), edited);
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["restore", "pending_exercise", "3"])
+        .current_dir(&dir)
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn endless_exercise_times_out() {
+    let dir = fixture_copy("timeout");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "endless"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("timed out after 1 seconds"))
+        .stdout(predicates::str::contains("Looping forever"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--format", "json"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(r#""outcome": "timed_out""#));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn exercise_exceeding_memory_limit_is_stopped() {
+    let dir = fixture_copy("limits");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "hog"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "exceeded its memory limit of 64 MiB",
+        ));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--format", "json"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(r#""outcome": "memory_exceeded""#));
+}
+
+#[test]
+fn exercise_output_is_compared_with_expected_output() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "padded"])
+        .current_dir("tests/fixture/output")
+        .assert()
+        .success();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "greeting"])
+        .current_dir("tests/fixture/output")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("-Hello world!"))
+        .stdout(predicates::str::contains("+Hello World!"));
+}
+
+#[test]
+fn verify_reports_wrong_output() {
+    let dir = fixture_copy("output");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--format", "json"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(r#""outcome": "passed""#))
+        .stdout(predicates::str::contains(r#""outcome": "wrong_output""#));
+}
+
+#[test]
+fn exercises_read_their_stdin() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "shout"])
+        .current_dir("tests/fixture/stdin")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("HELLO\nWORLD"));
+    let dir = fixture_copy("stdin");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn missing_stdin_file_fails_the_exercise() {
+    let dir = fixture_copy("stdin");
+    fs::remove_file(dir.join("words.txt")).unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--jobs", "2"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("The input of count is missing"))
+        .stderr(predicates::str::contains("panicked").not());
+}
+
+#[test]
+fn missing_expected_output_file_fails_the_exercise() {
+    let dir = fixture_copy("output");
+    fs::remove_file(dir.join("padded.txt")).unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "padded"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "The expected output of padded is missing",
+        ))
+        .stderr(predicates::str::contains("panicked").not());
+}
+
+#[test]
+fn run_with_stdin_override() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "shout", "--stdin", "other.txt"])
+        .current_dir("tests/fixture/stdin")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("QUIET PLEASE"));
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "count", "--stdin", "-"])
+        .current_dir("tests/fixture/stdin")
+        .write_stdin("a b c d e")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("5"));
+}
+
+#[test]
+fn compile_fail_exercises_expect_specific_errors() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "moved"])
+        .current_dir("tests/fixture/compile_fail")
+        .assert()
+        .success();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compiles"])
+        .current_dir("tests/fixture/compile_fail")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("compiled, but it should fail"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "mismatched"])
+        .current_dir("tests/fixture/compile_fail")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("failed with E0308"))
+        .stdout(predicates::str::contains("E0499"));
+    // An expected error doesn't make up for an unexpected one
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "mixed"])
+        .current_dir("tests/fixture/compile_fail")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("failed with E0382, E0499"));
+}
+
+#[test]
+fn exercises_requiring_fmt_pass_once_formatted() {
+    let dir = fixture_copy("fmt");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("isn't formatted"))
+        .stdout(predicates::str::contains("+    let x = 1;"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "messy"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("isn't formatted"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["fmt", "messy"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir(&dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn exercises_are_formatted_with_their_toolchain() {
+    let dir = fixture_copy("fmt");
+    let info = fs::read_to_string(dir.join("info.toml")).unwrap();
+    fs::write(
+        dir.join("info.toml"),
+        info.replace(
+            "path = \"messy.rs\"\n",
+            "path = \"messy.rs\"\ntoolchain = \"no-such-toolchain\"\n",
+        ),
+    )
+    .unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["fmt", "messy"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Failed to format messy"));
+}
+
+#[test]
+fn cargo_exercises_build_and_test_their_package() {
+    let dir = fixture_copy("cargo");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--nocapture", "run", "shapes"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("THE SQUARE IS MEASURED"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "broken"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Testing of broken failed"));
+    assert!(!dir.join("shapes/target").exists());
+
+    // Errors in any module of the package are reported
+    fs::write(dir.join("shapes/src/square.rs"), "pub struct Square(u32)").unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "shapes"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("src/square.rs:1:22"));
+}
+
+#[test]
+fn clippy_exercises_use_their_own_lints() {
+    let dir = fixture_copy("clippy");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "floats"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "strict comparison of `f32` or `f64`",
+        ));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "returns"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("42"));
+    // The generated packages live outside of the exercises
+    assert!(!dir.join("Cargo.toml").exists());
+}
+
+#[test]
+fn exercises_compile_with_their_edition_and_flags() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "keyword2015"])
+        .current_dir("tests/fixture/edition")
+        .assert()
+        .success();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "keyword2021"])
+        .current_dir("tests/fixture/edition")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("found keyword `async`"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "cfg"])
+        .current_dir("tests/fixture/edition")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("rustlings is set"));
+}
+
+#[test]
+fn rustc_can_be_overridden() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--rustc", "./no-such-rustc", "run", "cfg"])
+        .current_dir("tests/fixture/edition")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("We cannot find `rustc`"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "cfg"])
+        .env("RUSTLINGS_RUSTC", "./no-such-rustc")
+        .current_dir("tests/fixture/edition")
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn rustc_override_takes_precedence_over_toolchain_of_cargo_exercises() {
+    let dir = fixture_copy("cargo");
+    let info = fs::read_to_string(dir.join("info.toml")).unwrap();
+    fs::write(
+        dir.join("info.toml"),
+        info.replace(
+            "path = \"shapes\"\n",
+            "path = \"shapes\"\ntoolchain = \"no-such-toolchain\"\n",
+        ),
+    )
+    .unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--rustc", "rustc", "run", "shapes"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn clean_removes_stray_artifacts() {
+    let dir = fixture_copy("success");
+    fs::create_dir_all(dir.join("exercises/intro")).unwrap();
+    fs::write(dir.join("exercises/intro/intro1.rs"), "fn main() {}").unwrap();
+    fs::write(dir.join("exercises/intro/intro1"), b"\x7fELF binary").unwrap();
+    fs::write(dir.join("exercises/intro/notes.rs"), "").unwrap();
+    fs::write(dir.join("exercises/intro/notes"), "not a binary").unwrap();
+    fs::write(dir.join("temp_6591_ThreadId1"), b"\x7fELF binary").unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["clean", "--dry-run"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Would remove temp_6591_ThreadId1",
+        ));
+    assert!(dir.join("temp_6591_ThreadId1").exists());
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("clean")
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Removed exercises/intro/intro1"));
+    assert!(!dir.join("temp_6591_ThreadId1").exists());
+    assert!(!dir.join("exercises/intro/intro1").exists());
+    assert!(dir.join("exercises/intro/intro1.rs").exists());
+    assert!(dir.join("exercises/intro/notes").exists());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn interrupt_kills_the_running_exercise() {
+    use std::process::Stdio;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    // Whether the process is gone, or only waits to be reaped
+    fn is_dead(pid: &str) -> bool {
+        match fs::read_to_string(format!("/proc/{pid}/stat")) {
+            Ok(stat) => stat.rsplit(") ").next().is_some_and(|s| s.starts_with('Z')),
+            Err(_) => true,
+        }
+    }
+
+    let dir = fixture_copy("timeout");
+    let info = fs::read_to_string(dir.join("info.toml")).unwrap();
+    fs::write(dir.join("info.toml"), info.replace("timeout = 1\n", "")).unwrap();
+    fs::write(
+        dir.join("endless.rs"),
+        "fn main() {\n    std::fs::write(\"pid\", std::process::id().to_string()).unwrap();\n    \
+         loop {\n        std::thread::sleep(std::time::Duration::from_millis(10));\n    }\n}\n",
+    )
+    .unwrap();
+
+    let mut rustlings = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "endless"])
+        .current_dir(&dir)
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap();
+    let start = Instant::now();
+    let pid = loop {
+        if let Ok(pid) = fs::read_to_string(dir.join("pid")) {
+            break pid;
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(30),
+            "The exercise never ran"
+        );
+        thread::sleep(Duration::from_millis(50));
+    };
+    Command::new("kill")
+        .args(["-INT", &rustlings.id().to_string()])
+        .assert()
+        .success();
+    let status = rustlings.wait().unwrap();
+    assert_eq!(status.code(), Some(130));
+
+    let start = Instant::now();
+    while !is_dead(&pid) {
+        assert!(
+            start.elapsed() < Duration::from_secs(5),
+            "The exercise outlived rustlings"
+        );
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn clean_only_removes_session_directories() {
+    let dir = fixture_copy("success");
+    // No process can have these IDs
+    let tmp = dir.join("tmp");
+    let session = tmp.join("rustlings-4000000001");
+    let checkout = tmp.join("rustlings-4000000002");
+    fs::create_dir_all(&session).unwrap();
+    fs::write(session.join(".rustlings-session"), "").unwrap();
+    fs::create_dir_all(&checkout).unwrap();
+    fs::write(checkout.join("info.toml"), "").unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("clean")
+        .env("TMPDIR", &tmp)
+        .current_dir(&dir)
+        .assert()
+        .success();
+    assert!(!session.exists());
+    assert!(checkout.join("info.toml").exists());
+}
+
+#[test]
+fn configuration_files_set_defaults() {
+    let dir = fixture_copy("timeout");
+    let info = fs::read_to_string(dir.join("info.toml")).unwrap();
+    fs::write(dir.join("info.toml"), info.replace("timeout = 1\n", "")).unwrap();
+    fs::write(dir.join("rustlings.toml"), "timeout = 2\n").unwrap();
+    let user_config = dir.join("config-home/rustlings");
+    fs::create_dir_all(&user_config).unwrap();
+    fs::write(
+        user_config.join("config.toml"),
+        "timeout = 5\nclear_screen = false\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("config")
+        .env("XDG_CONFIG_HOME", dir.join("config-home"))
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::is_match(r"timeout = 2 +# rustlings.toml").unwrap())
+        .stdout(predicates::str::is_match(r"clear_screen = false +# .*config.toml").unwrap())
+        .stdout(predicates::str::is_match(r"verbose = false +# default").unwrap());
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "endless"])
+        .env("XDG_CONFIG_HOME", dir.join("config-home"))
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("timed out after 2 seconds"));
+
+    fs::write(dir.join("rustlings.toml"), "emoji = false\ntimeout = 1\n").unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "endless"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("! endless.rs timed out"))
+        .stdout(predicates::str::contains("⚠️").not());
+
+    fs::write(dir.join("rustlings.toml"), "timeout = \"soon\"\n").unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("config")
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Failed to parse rustlings.toml"));
+}
+
+#[test]
+fn cached_outcomes_follow_the_configuration() {
+    let dir = fixture_copy("failure");
+    fs::write(dir.join("rustlings.toml"), "color = \"always\"\n").unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("\x1b["));
+    fs::write(dir.join("rustlings.toml"), "color = \"never\"\n").unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("\x1b[").not());
+}
+
+#[cfg(unix)]
+#[test]
+fn edit_opens_exercises_where_there_is_work_to_do() {
+    use std::os::unix::fs::PermissionsExt;
+
+    // An editor named like vim that prints the arguments it got,
+    // quoted like in a shell because its path has a space in it
+    fn fake_editor(dir: &Path) -> String {
+        let editor = dir.join("my bin/vim");
+        fs::create_dir_all(editor.parent().unwrap()).unwrap();
+        fs::write(&editor, "#!/bin/sh\necho \"editing $*\"\n").unwrap();
+        fs::set_permissions(&editor, fs::Permissions::from_mode(0o755)).unwrap();
+        format!("'{}'", editor.display())
+    }
+
+    let dir = fixture_copy("state");
+    let editor = fake_editor(&dir);
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["edit", "pending_exercise"])
+        .env("XDG_CONFIG_HOME", &dir)
+        .env_remove("VISUAL")
+        .env("EDITOR", &editor)
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("editing +3 pending_exercise.rs"));
+
+    let dir = fixture_copy("failure");
+    let editor = fake_editor(&dir);
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["edit", "compFailure"])
+        .env("XDG_CONFIG_HOME", &dir)
+        .env_remove("VISUAL")
+        .env("EDITOR", &editor)
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("editing +3 compFailure.rs"));
+
+    let dir = fixture_copy("cargo");
+    let editor = fake_editor(&dir);
+    fs::write(
+        dir.join("broken/src/double.rs"),
+        "pub fn double(n: i32) -> i32 {\n    n * \"2\"\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("rustlings.toml"),
+        format!("editor = \"{editor} --nofork\"\n"),
+    )
+    .unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["edit", "broken"])
+        .env("XDG_CONFIG_HOME", &dir)
+        .env("EDITOR", "no-such-editor")
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "editing --nofork +2 broken/src/double.rs",
+        ));
+
+    let dir = fixture_copy("state");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["edit", "pending_exercise"])
+        .env("XDG_CONFIG_HOME", &dir)
+        .env_remove("VISUAL")
+        .env_remove("EDITOR")
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("There's no editor"));
+}